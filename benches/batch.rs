@@ -0,0 +1,46 @@
+//! Updating 10k scalar tweens as a `Batch` versus as boxed `Single`s in a
+//! `Parallel`.
+
+extern crate test;
+extern crate tween;
+
+use test::Bencher;
+
+use tween::{Tween, from_to, par};
+use tween::batch::{Batch, Quint};
+use tween::ease::{quint, InOut};
+
+static N: uint = 10000;
+static STEPS: uint = 10;
+
+#[bench]
+fn batch(b: &mut Bencher) {
+    let mut batch = Batch::with_capacity(N);
+    for i in range(0, N) {
+        batch.add(0., i as f64, STEPS as f64, Quint, InOut);
+    }
+    let mut out = Vec::from_elem(N, 0f64);
+
+    b.iter(|| {
+        batch.reset();
+        for _ in range(0, STEPS) {
+            batch.update(1., out.as_mut_slice());
+        }
+    });
+}
+
+#[bench]
+fn boxed(b: &mut Bencher) {
+    let mut vals = Vec::from_elem(N, 0f64);
+    let tweens = vals.iter_mut().enumerate().map(|(i, v)| {
+        box from_to(v as *mut f64, 0., i as f64, quint(), InOut, STEPS as f64) as Box<Tween + 'static>
+    }).collect();
+    let mut tree = par(tweens);
+
+    b.iter(|| {
+        tree.reset();
+        for _ in range(0, STEPS) {
+            tree.update(1.);
+        }
+    });
+}
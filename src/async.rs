@@ -0,0 +1,152 @@
+//! Waiting for tweens without polling them by hand. Only available with the
+//! `async` feature.
+//!
+//! Nothing here needs a particular event loop: completion is signalled over
+//! channels, and sampled values are pulled from a clock. Both can be polled
+//! without blocking, from the task driving the tweens, or waited for from
+//! another task.
+
+use std::comm::{Empty, Disconnected};
+use std::sync::Future;
+
+use {Tween, Access};
+use clock::Clock;
+use manager::{Manager, TweenId};
+
+/// Completion of tweens owned by a manager.
+pub trait Completion {
+    /// The completion of a tween, `true` once it is done, or right away if
+    /// it already is, or `false` if it was removed before finishing or isn't
+    /// known.
+    fn finished(&mut self, id: TweenId) -> Finished;
+}
+
+impl Completion for Manager {
+    fn finished(&mut self, id: TweenId) -> Finished {
+        Finished {
+            rx: self.on_finish(id),
+            result: None
+        }
+    }
+}
+
+/// The completion of one tween, see `Completion`.
+pub struct Finished {
+    rx: Receiver<()>,
+    result: Option<bool>
+}
+
+impl Finished {
+    /// The result if it is known, `None` while the tween is running. Never
+    /// blocks, so it can be called between updates on the task driving the
+    /// manager.
+    pub fn poll(&mut self) -> Option<bool> {
+        if self.result.is_none() {
+            self.result = match self.rx.try_recv() {
+                Ok(()) => Some(true),
+                Err(Empty) => None,
+                Err(Disconnected) => Some(false)
+            };
+        }
+        self.result
+    }
+
+    /// Wait for the result. The manager isn't `Send`, so unless the tween
+    /// is already done this must be called from another task than the one
+    /// updating the manager, or it waits forever.
+    pub fn get(mut self) -> bool {
+        match self.poll() {
+            Some(r) => r,
+            None => self.rx.recv_opt().is_ok()
+        }
+    }
+
+    /// A `Future` of the result, with the same caveat as `get`.
+    pub fn into_future(self) -> Future<bool> {
+        Future::from_fn(proc() self.get())
+    }
+}
+
+/// What `Samples::poll` found.
+#[deriving(PartialEq, Show)]
+pub enum Polled<T> {
+    /// A sample, at this time.
+    Sample(f64, T),
+    /// The next sample isn't due yet.
+    Pending,
+    /// The tween is done, there are no more samples.
+    Ended
+}
+
+/// A stream driving a tween by a clock, and yielding `(time, value)` pairs
+/// read through an accessor at a fixed interval, until the tween is done.
+/// `poll` takes the next sample if it is due, iterating waits for it.
+pub struct Samples<A, C> {
+    tween: Box<Tween + 'static>,
+    acc: A,
+    clock: C,
+    interval: f64,
+    next: f64,
+    last: f64
+}
+
+impl<A, C> Samples<A, C> {
+    pub fn clock(&mut self) -> &mut C {
+        &mut self.clock
+    }
+}
+
+impl<T, A: Access<T>, C: Clock> Samples<A, C> {
+    /// The next sample if the clock reached its time, without waiting.
+    /// Samples whose time passed since the last poll are skipped.
+    pub fn poll(&mut self) -> Polled<T> {
+        if self.tween.done() {
+            return Ended;
+        }
+        let now = self.clock.now();
+        if now < self.next + self.interval {
+            return Pending;
+        }
+        while self.next + self.interval <= now {
+            self.next += self.interval;
+        }
+        let (time, val) = self.sample();
+        Sample(time, val)
+    }
+
+    /// Update the tween to the clock's time and read the value.
+    fn sample(&mut self) -> (f64, T) {
+        let now = self.clock.now();
+        self.tween.update(now - self.last);
+        self.last = now;
+        (now, self.acc.get())
+    }
+}
+
+impl<T, A: Access<T>, C: Clock> Iterator<(f64, T)> for Samples<A, C> {
+    fn next(&mut self) -> Option<(f64, T)> {
+        if self.tween.done() {
+            return None;
+        }
+        self.next += self.interval;
+        self.clock.wait_until(self.next);
+        Some(self.sample())
+    }
+}
+
+/// Sample the value behind `acc` every `interval`, while `tween` is driven
+/// by `clock`. Time starts at the clock's current time. `interval` has to
+/// be positive.
+pub fn samples<T, A: Access<T>, C: Clock>
+(tween: Box<Tween + 'static>, acc: A, clock: C, interval: f64) -> Samples<A, C> {
+    assert!(interval > 0., "the sampling interval must be positive, got {}", interval);
+    let now = clock.now();
+    Samples {
+        tween: tween,
+        acc: acc,
+        clock: clock,
+        interval: interval,
+        next: now,
+        last: now
+    }
+}
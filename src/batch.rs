@@ -0,0 +1,159 @@
+//! Many scalar tweens at once, for particle systems and the like.
+//!
+//! Instead of one boxed tween per value, a `Batch` keeps every field of its
+//! tweens in its own array and updates them in a few tight loops, writing
+//! the results into a slice the caller owns. Only the built-in easings are
+//! available, selected by `EaseKind`.
+
+use ease;
+use ease::{Ease, Mode};
+
+/// The built-in easings, for tweens in a `Batch`.
+#[deriving(Clone, PartialEq, Show)]
+pub enum EaseKind {
+    Linear,
+    Quad,
+    Cubic,
+    Quart,
+    Quint,
+    Sine,
+    Circ,
+    Back,
+    Elastic,
+    Bounce
+}
+
+impl EaseKind {
+    #[inline]
+    fn ease(&self, mode: Mode, t: f64) -> f64 {
+        match *self {
+            Linear => ease::linear().ease(mode, t),
+            Quad => ease::quad().ease(mode, t),
+            Cubic => ease::cubic().ease(mode, t),
+            Quart => ease::quart().ease(mode, t),
+            Quint => ease::quint().ease(mode, t),
+            Sine => ease::sine().ease(mode, t),
+            Circ => ease::circ().ease(mode, t),
+            Back => ease::back().ease(mode, t),
+            Elastic => ease::elastic().ease(mode, t),
+            Bounce => ease::bounce().ease(mode, t)
+        }
+    }
+}
+
+/// A structure of arrays of scalar tweens. Tweens are addressed by their
+/// index, which is also their index in the output slice.
+#[deriving(Clone)]
+pub struct Batch {
+    start: Vec<f64>,
+    end: Vec<f64>,
+    duration: Vec<f64>,
+    elapsed: Vec<f64>,
+    kind: Vec<EaseKind>,
+    mode: Vec<Mode>,
+    /// Scratch space for the normalized times and alphas.
+    alpha: Vec<f64>
+}
+
+impl Batch {
+    pub fn new() -> Batch {
+        Batch::with_capacity(0)
+    }
+
+    pub fn with_capacity(n: uint) -> Batch {
+        Batch {
+            start: Vec::with_capacity(n),
+            end: Vec::with_capacity(n),
+            duration: Vec::with_capacity(n),
+            elapsed: Vec::with_capacity(n),
+            kind: Vec::with_capacity(n),
+            mode: Vec::with_capacity(n),
+            alpha: Vec::with_capacity(n)
+        }
+    }
+
+    /// Add a tween from `start` to `end`, and return its index. A tween with
+    /// a duration of 0 is done, and ends up at `end` on the next update.
+    pub fn add(&mut self, start: f64, end: f64, duration: f64, kind: EaseKind, mode: Mode) -> uint {
+        self.start.push(start);
+        self.end.push(end);
+        self.duration.push(duration);
+        self.elapsed.push(0.);
+        self.kind.push(kind);
+        self.mode.push(mode);
+        self.alpha.push(0.);
+        self.start.len() - 1
+    }
+
+    /// Remove a tween by moving the last one into its place, which changes
+    /// the index of the last tween to `i`.
+    pub fn swap_remove(&mut self, i: uint) {
+        self.start.swap_remove(i);
+        self.end.swap_remove(i);
+        self.duration.swap_remove(i);
+        self.elapsed.swap_remove(i);
+        self.kind.swap_remove(i);
+        self.mode.swap_remove(i);
+        self.alpha.swap_remove(i);
+    }
+
+    pub fn len(&self) -> uint {
+        self.start.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start.is_empty()
+    }
+
+    /// Returns true if the tween at `i` is done.
+    #[inline]
+    pub fn done(&self, i: uint) -> bool {
+        self.elapsed[i] >= self.duration[i]
+    }
+
+    /// Returns true if every tween is done.
+    pub fn all_done(&self) -> bool {
+        range(0, self.len()).all(|i| self.done(i))
+    }
+
+    /// Restart every tween.
+    pub fn reset(&mut self) {
+        for e in self.elapsed.iter_mut() {
+            *e = 0.;
+        }
+    }
+
+    /// Advance every tween by `delta` and write the values to `out`, which
+    /// must be as long as the batch. A negative `delta` moves back, but not
+    /// before the start. The values are the same as those of a `Single`
+    /// with the same easing.
+    pub fn update(&mut self, delta: f64, out: &mut [f64]) {
+        let n = self.len();
+        assert!(out.len() == n, "output has {} values, batch has {} tweens", out.len(), n);
+
+        let elapsed = self.elapsed.as_mut_slice();
+        let duration = self.duration.as_slice();
+        let alpha = self.alpha.as_mut_slice();
+
+        // the first and last loop are plain arithmetic, so they vectorize,
+        // unlike the easing in between
+        for i in range(0, n) {
+            let remain = duration[i] - elapsed[i];
+            let e = elapsed[i] + if remain < delta {remain} else {delta};
+            let e = if e > 0. {e} else {0.};
+            elapsed[i] = e;
+            // a tween without duration is at its end right away
+            alpha[i] = if duration[i] > 0. {e / duration[i]} else {1.};
+        }
+
+        for i in range(0, n) {
+            alpha[i] = self.kind[i].ease(self.mode[i], alpha[i]);
+        }
+
+        let start = self.start.as_slice();
+        let end = self.end.as_slice();
+        for i in range(0, n) {
+            out[i] = start[i] + (end[i] - start[i]) * alpha[i];
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Weighted blending of several tween trees driving the same properties,
+//! e.g. to fade from one animation to another instead of switching hard.
+//!
+//! Every layer of a `Blend` writes into its own `Slot`s instead of the real
+//! properties. After each update, the slots of all layers are mixed by the
+//! layer weights and the result is written to the targets.
+//!
+//! The slots are shared by all clones of a blend, e.g. the ones made by
+//! `yoyo`. Every clone keeps its own values, and only loads them into the
+//! slots while it updates, so clones don't overwrite each other.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use {Tween, Tweenable, Access, MulWithF64};
+use ease::{Ease, Mode};
+use props::{MultiProperty, prop};
+use snapshot::{Snapshot, Reader};
+use describe::Node;
+
+/// A value a blended layer writes to, in place of the real property.
+#[deriving(Clone)]
+pub struct Slot<T> {
+    val: Rc<Cell<T>>
+}
+
+impl<T: Copy> Slot<T> {
+    pub fn new(val: T) -> Slot<T> {
+        Slot {val: Rc::new(Cell::new(val))}
+    }
+}
+
+impl<T: Copy> Access<T> for Slot<T> {
+    #[inline]
+    fn get(&self) -> T {
+        self.val.get()
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        self.val.set(val);
+    }
+}
+
+#[deriving(Clone)]
+struct Layer<T> {
+    tween: Box<Tween + 'static>,
+    slots: Vec<Slot<T>>,
+    weight: Slot<f64>,
+    /// The values of this blend, loaded into the slots for updates.
+    values: Vec<T>,
+    weight_value: f64,
+    /// The values and weight to go back to on reset.
+    initial_values: Vec<T>,
+    initial_weight: f64
+}
+
+impl<T: Copy> Layer<T> {
+    fn load(&mut self) {
+        for (s, &v) in self.slots.iter_mut().zip(self.values.iter()) {
+            s.set(v);
+        }
+    }
+
+    fn store(&mut self) {
+        for (v, s) in self.values.iter_mut().zip(self.slots.iter()) {
+            *v = s.get();
+        }
+    }
+}
+
+/// Mixes the values of several layers into a set of target properties.
+/// Weights don't need to add up to 1, the mix is normalized. While all
+/// weights are 0, or any is NaN, the targets are left alone.
+#[deriving(Clone)]
+pub struct Blend<T, A> {
+    targets: Vec<A>,
+    layers: Vec<Layer<T>>,
+    /// Tweens driving the weights, updated before the layers.
+    weights: Vec<Box<Tween + 'static>>,
+    /// Whether it was updated since the last reset.
+    started: bool
+}
+
+impl<T: Tweenable + 'static, A: Access<T>> Blend<T, A> {
+    pub fn new(targets: Vec<A>) -> Blend<T, A> {
+        Blend {
+            targets: targets,
+            layers: Vec::new(),
+            weights: Vec::new(),
+            started: false
+        }
+    }
+
+    /// Add a layer. `build` gets one slot per target, in the order of the
+    /// targets, and returns the tween driving them. The slots start out at
+    /// the targets' current values. Returns the weight of the layer, for
+    /// tweens given to `drive_weight`. To change it directly, use
+    /// `set_weight`.
+    pub fn add_layer(&mut self, weight: f64, build: |&[Slot<T>]| -> Box<Tween + 'static>)
+    -> Slot<f64> {
+        let values: Vec<T> = self.targets.iter().map(|t| t.get()).collect();
+        let slots: Vec<Slot<T>> = values.iter().map(|&v| Slot::new(v)).collect();
+        let tween = build(slots.as_slice());
+        let slot = Slot::new(weight);
+        self.layers.push(Layer {
+            tween: tween,
+            slots: slots,
+            weight: slot.clone(),
+            values: values.clone(),
+            weight_value: weight,
+            initial_values: values,
+            initial_weight: weight
+        });
+        slot
+    }
+
+    /// Set the weight of the `layer`th layer added.
+    pub fn set_weight(&mut self, layer: uint, weight: f64) {
+        assert!(layer < self.layers.len(), "no layer {} in a blend of {} layers", layer, self.layers.len());
+        self.layers[layer].weight_value = weight;
+    }
+
+    /// The weights of all layers, in the order they were added.
+    pub fn weights(&self) -> Vec<f64> {
+        self.layers.iter().map(|l| l.weight_value).collect()
+    }
+
+    /// Add a tween that drives one or more layer weights.
+    pub fn drive_weight(&mut self, tween: Box<Tween + 'static>) {
+        self.weights.push(tween);
+    }
+
+    fn mix(&mut self) {
+        let total = self.layers.iter().fold(0., |a, l| a + l.weight_value);
+        // also leaves the targets alone if a weight is NaN
+        if !(total > 0.) {
+            return;
+        }
+        for (i, target) in self.targets.iter_mut().enumerate() {
+            let mut val: Option<T> = None;
+            for l in self.layers.iter() {
+                let part = l.values[i].mul_with_f64(l.weight_value / total);
+                val = Some(match val {
+                    Some(v) => v + part,
+                    None => part
+                });
+            }
+            match val {
+                Some(v) => target.set(v),
+                None => {}
+            }
+        }
+    }
+}
+
+impl<T: Tweenable + 'static, A: Access<T> + 'static> Tween for Blend<T, A> {
+    /// The max remaining time of all layers and weight tweens.
+    #[inline]
+    fn remaining(&self) -> f64 {
+        let layers = self.layers.iter().map(|l| l.tween.remaining());
+        let weights = self.weights.iter().map(|w| w.remaining());
+        layers.chain(weights).fold(0., |a, b| if b > a {b} else {a})
+    }
+
+    /// Also sets the weights and the values of the layers back to the ones
+    /// they were added with.
+    #[inline]
+    fn reset(&mut self) {
+        self.started = false;
+        for w in self.weights.iter_mut() {
+            w.reset();
+        }
+        for l in self.layers.iter_mut() {
+            l.tween.reset();
+            l.values = l.initial_values.clone();
+            l.weight_value = l.initial_weight;
+        }
+    }
+
+    /// Like in a `Parallel`, weight and layer tweens that are done are
+    /// left alone unless going backwards, except on the first update.
+    fn update(&mut self, delta: f64) -> f64 {
+        let remain = self.remaining();
+        let skip_done = self.started && delta >= 0.;
+        for l in self.layers.iter_mut() {
+            let w = l.weight_value;
+            l.weight.set(w);
+        }
+        for w in self.weights.iter_mut() {
+            if skip_done && w.done() {
+                continue;
+            }
+            let r = w.remaining();
+            w.update(if r < delta {r} else {delta});
+        }
+        for l in self.layers.iter_mut() {
+            l.weight_value = l.weight.get();
+            if skip_done && l.tween.done() {
+                continue;
+            }
+            l.load();
+            let r = l.tween.remaining();
+            l.tween.update(if r < delta {r} else {delta});
+            l.store();
+        }
+        self.started = true;
+        self.mix();
+        delta - remain
+    }
+
+    /// Saves the slot values and weights as well, as they are only written
+    /// by tweens that are still running.
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_bool(self.started);
+        for w in self.weights.iter() {
+            w.save(snap);
+        }
+        for l in self.layers.iter() {
+            l.tween.save(snap);
+            snap.push_f64(l.weight_value);
+            for &v in l.values.iter() {
+                snap.push_value(v);
+            }
+        }
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.started = snap.bool();
+        for w in self.weights.iter_mut() {
+            w.load(snap);
+        }
+        for l in self.layers.iter_mut() {
+            l.tween.load(snap);
+            l.weight_value = snap.f64();
+            for v in l.values.iter_mut() {
+                *v = snap.value();
+            }
+        }
+    }
+
+    /// The weight tweens come first, then the layers.
+    fn describe(&self) -> Node {
+        let weights = self.weights.iter().map(|w| w.describe());
+        let layers = self.layers.iter().map(|l| l.tween.describe());
+        let children: Vec<Node> = weights.chain(layers).collect();
+        let elapsed = children.iter().fold(0., |a, c| if c.elapsed > a {c.elapsed} else {a});
+        Node::new("Blend", elapsed, self.remaining())
+            .children(children)
+    }
+}
+
+/// Fade from one tween tree to another over `duration`. `from` and `to`
+/// are built against slots for `targets`, see `Blend::add_layer`. The
+/// weights follow `ease` in `mode`, always add up to 1, and are exactly 0
+/// and 1 after the last update.
+pub fn crossfade<T: Tweenable + 'static, A: Access<T>, E: Ease + 'static>
+(targets: Vec<A>,
+ from: |&[Slot<T>]| -> Box<Tween + 'static>,
+ to: |&[Slot<T>]| -> Box<Tween + 'static>,
+ duration: f64, ease: E, mode: Mode)
+-> Blend<T, A> {
+    let mut blend = Blend::new(targets);
+    let out = blend.add_layer(1., from);
+    let inc = blend.add_layer(0., to);
+    let weights = MultiProperty::new(vec![prop(out, 1., 0.), prop(inc, 0., 1.)], ease, mode, duration);
+    blend.drive_weight(box weights as Box<Tween + 'static>);
+    blend
+}
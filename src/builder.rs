@@ -0,0 +1,184 @@
+//! Building tweens by naming their parts instead of by position.
+//!
+//! ```rust
+//! let tw = on(x.clone()).from(0.).to(100.).over(2.)
+//!     .ease(quint()).mode(InOut)
+//!     .delay(0.5).repeat(3).yoyo()
+//!     .on_complete(done)
+//!     .build();
+//! ```
+//!
+//! The result is made of the same tweens as the positional functions in
+//! the crate root build, e.g. `Single`, `Sequence`, `Repeat` and `Pause`.
+//!
+//! Building starts with the free function `on`, not a `Tween::on`: a static
+//! method of the trait would have no `Self` to be called on, so `Tween::on(x)`
+//! couldn't be written.
+
+use {Tween, Tweenable, Access, Single, Repeat, from_to, seq, exec, pause, rep};
+use ease::{Ease, Mode, In, Out, InOut, OutIn, LinearEase, linear};
+
+#[deriving(Clone)]
+enum Repeats {
+    Times(uint),
+    Forever
+}
+
+/// Collects the parts of a tween, see the module documentation.
+#[deriving(Clone)]
+pub struct Builder<T, A, E> {
+    acc: A,
+    from: Option<T>,
+    to: Option<T>,
+    duration: f64,
+    ease: E,
+    mode: Mode,
+    delay: f64,
+    repeats: Repeats,
+    yoyo: bool,
+    on_complete: Option<fn()>
+}
+
+/// Start building a tween of the value behind `acc`. Unless changed, it
+/// goes from and to the current value, linearly, in one time unit.
+pub fn on<T: Tweenable + 'static, A: Access<T> + 'static>(acc: A) -> Builder<T, A, LinearEase> {
+    Builder {
+        acc: acc,
+        from: None,
+        to: None,
+        duration: 1.,
+        ease: linear(),
+        mode: In,
+        delay: 0.,
+        repeats: Times(0),
+        yoyo: false,
+        on_complete: None
+    }
+}
+
+/// The mode that plays an easing backwards when tweening from end to start.
+fn reversed(mode: Mode) -> Mode {
+    match mode {
+        In => Out,
+        Out => In,
+        InOut => InOut,
+        OutIn => OutIn
+    }
+}
+
+impl<T: Tweenable + 'static, A: Access<T> + 'static, E: Ease + 'static> Builder<T, A, E> {
+    /// The value to start at, instead of the value when building.
+    pub fn from(self, start: T) -> Builder<T, A, E> {
+        Builder {from: Some(start), ..self}
+    }
+
+    /// The value to end at, instead of the value when building.
+    pub fn to(self, end: T) -> Builder<T, A, E> {
+        Builder {to: Some(end), ..self}
+    }
+
+    /// The duration of one play.
+    pub fn over(self, duration: f64) -> Builder<T, A, E> {
+        Builder {duration: duration, ..self}
+    }
+
+    pub fn ease<F: Ease>(self, ease: F) -> Builder<T, A, F> {
+        Builder {
+            acc: self.acc,
+            from: self.from,
+            to: self.to,
+            duration: self.duration,
+            ease: ease,
+            mode: self.mode,
+            delay: self.delay,
+            repeats: self.repeats,
+            yoyo: self.yoyo,
+            on_complete: self.on_complete
+        }
+    }
+
+    pub fn mode(self, mode: Mode) -> Builder<T, A, E> {
+        Builder {mode: mode, ..self}
+    }
+
+    /// Wait before the first play.
+    pub fn delay(self, time: f64) -> Builder<T, A, E> {
+        Builder {delay: time, ..self}
+    }
+
+    /// Play `n` more times after the first play.
+    pub fn repeat(self, n: uint) -> Builder<T, A, E> {
+        Builder {repeats: Times(n), ..self}
+    }
+
+    /// Play over and over again.
+    pub fn repeat_forever(self) -> Builder<T, A, E> {
+        Builder {repeats: Forever, ..self}
+    }
+
+    /// Play every other repetition backwards.
+    pub fn yoyo(self) -> Builder<T, A, E> {
+        Builder {yoyo: true, ..self}
+    }
+
+    /// Call `f` once the last play is done. Never called when repeating
+    /// forever.
+    pub fn on_complete(self, f: fn()) -> Builder<T, A, E> {
+        Builder {on_complete: Some(f), ..self}
+    }
+
+    /// A single play, ignoring delay, repetitions and the completion
+    /// function.
+    pub fn single(&self) -> Single<T, A, E> {
+        let start = match self.from { Some(v) => v, None => self.acc.get() };
+        let end = match self.to { Some(v) => v, None => self.acc.get() };
+        from_to(self.acc.clone(), start, end, self.ease.clone(), self.mode, self.duration)
+    }
+
+    /// A single play backwards.
+    fn single_back(&self) -> Single<T, A, E> {
+        let start = match self.from { Some(v) => v, None => self.acc.get() };
+        let end = match self.to { Some(v) => v, None => self.acc.get() };
+        from_to(self.acc.clone(), end, start, self.ease.clone(), reversed(self.mode), self.duration)
+    }
+
+    /// Build the tween.
+    pub fn build(self) -> Box<Tween + 'static> {
+        let fwd = box self.single() as Box<Tween + 'static>;
+        let back = box self.single_back() as Box<Tween + 'static>;
+
+        let mut parts = Vec::new();
+        if self.delay > 0. {
+            parts.push(pause(self.delay));
+        }
+        match self.repeats {
+            Times(n) => {
+                let plays = n + 1;
+                if self.yoyo {
+                    // there and back again, and once more there for an odd count
+                    if plays / 2 > 0 {
+                        parts.push(box Repeat::times(seq(vec![fwd.clone(), back]), plays / 2) as Box<Tween + 'static>);
+                    }
+                    if plays % 2 == 1 {
+                        parts.push(fwd);
+                    }
+                } else if plays > 1 {
+                    parts.push(box Repeat::times(fwd, plays) as Box<Tween + 'static>);
+                } else {
+                    parts.push(fwd);
+                }
+                match self.on_complete {
+                    Some(f) => parts.push(exec(f)),
+                    None => {}
+                }
+            }
+            Forever => parts.push(if self.yoyo {rep(seq(vec![fwd, back]))} else {rep(fwd)})
+        }
+
+        if parts.len() == 1 {
+            parts.pop().unwrap()
+        } else {
+            seq(parts)
+        }
+    }
+}
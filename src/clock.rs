@@ -0,0 +1,278 @@
+//! Sources of time for driving tweens, and a `Driver` advancing tweens in
+//! fixed steps.
+//!
+//! Times given as `f64` are in seconds wherever a `Duration` is converted,
+//! see `secs` and `duration`.
+
+use std::cmp;
+use std::i64;
+use std::io::timer::sleep;
+use std::time::Duration;
+use time::precise_time_ns;
+
+/// A `Duration` in seconds.
+#[inline]
+pub fn secs(d: Duration) -> f64 {
+    match d.num_nanoseconds() {
+        Some(ns) => ns as f64 / 1e9,
+        // too many nanoseconds for an i64, so precision doesn't matter
+        None => d.num_milliseconds() as f64 / 1e3
+    }
+}
+
+/// A number of seconds as a `Duration`.
+#[inline]
+pub fn duration(secs: f64) -> Duration {
+    Duration::nanoseconds((secs * 1e9) as i64)
+}
+
+/// A source of time, in the same unit the tweens are updated with.
+pub trait Clock {
+    /// The current time.
+    fn now(&self) -> f64;
+
+    /// Return once `now` is at least `time`.
+    fn wait_until(&mut self, time: f64);
+}
+
+/// A clock that only moves when told to. Waiting on it jumps straight to
+/// the requested time, which makes it useful for tests.
+#[deriving(Clone)]
+pub struct ManualClock {
+    time: f64
+}
+
+impl ManualClock {
+    pub fn new() -> ManualClock {
+        ManualClock {time: 0.}
+    }
+
+    /// Move the clock forward by `delta`.
+    pub fn advance(&mut self, delta: f64) {
+        self.time += delta;
+    }
+
+    /// Set the clock to `time`.
+    pub fn set(&mut self, time: f64) {
+        self.time = time;
+    }
+}
+
+impl Clock for ManualClock {
+    #[inline]
+    fn now(&self) -> f64 {
+        self.time
+    }
+
+    #[inline]
+    fn wait_until(&mut self, time: f64) {
+        if time > self.time {
+            self.time = time;
+        }
+    }
+}
+
+/// The real time that has passed since the clock was created, in seconds.
+#[deriving(Clone)]
+pub struct SystemClock {
+    start: u64
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock {start: precise_time_ns()}
+    }
+}
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> f64 {
+        (precise_time_ns() - self.start) as f64 / 1e9
+    }
+
+    fn wait_until(&mut self, time: f64) {
+        let left = time - self.now();
+        if left > 0. {
+            sleep(duration(left));
+        }
+    }
+}
+
+/// A clock counting whole ticks of a fixed rate, e.g. the frames of a
+/// lockstep simulation. The time is computed from the tick count instead
+/// of being summed up, so it is the same on every machine after the same
+/// number of ticks.
+#[deriving(Clone)]
+pub struct TickClock {
+    ticks: u64,
+    rate: u32
+}
+
+impl TickClock {
+    /// A clock at tick 0, with `rate` ticks per time unit, which must be
+    /// positive.
+    pub fn new(rate: u32) -> TickClock {
+        assert!(rate > 0, "the rate of a tick clock must be positive");
+        TickClock {ticks: 0, rate: rate}
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Move the clock forward by `n` ticks. Returns the time that passed,
+    /// to update tweens with.
+    pub fn advance(&mut self, n: u64) -> f64 {
+        let before = self.now();
+        self.ticks += n;
+        self.now() - before
+    }
+}
+
+impl Clock for TickClock {
+    #[inline]
+    fn now(&self) -> f64 {
+        self.ticks as f64 / self.rate as f64
+    }
+
+    /// Jumps to the first tick at or after `time`.
+    fn wait_until(&mut self, time: f64) {
+        let tick = (time * self.rate as f64).ceil() as u64;
+        if tick > self.ticks {
+            self.ticks = tick;
+        }
+    }
+}
+
+/// Runs another clock faster or slower, e.g. for slow motion.
+/// Changing the scale doesn't make the time jump.
+#[deriving(Clone)]
+pub struct ScaledClock<C> {
+    clock: C,
+    scale: f64,
+    base: f64,
+    base_scaled: f64
+}
+
+impl<C: Clock> ScaledClock<C> {
+    pub fn new(clock: C, scale: f64) -> ScaledClock<C> {
+        let now = clock.now();
+        ScaledClock {
+            clock: clock,
+            scale: scale,
+            base: now,
+            base_scaled: now
+        }
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Change the scale from now on. A scale of `0` pauses the clock.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.base_scaled = self.now();
+        self.base = self.clock.now();
+        self.scale = scale;
+    }
+
+    pub fn inner(&mut self) -> &mut C {
+        &mut self.clock
+    }
+}
+
+impl<C: Clock> Clock for ScaledClock<C> {
+    #[inline]
+    fn now(&self) -> f64 {
+        self.base_scaled + (self.clock.now() - self.base) * self.scale
+    }
+
+    /// Returns immediately if the clock is paused, as it would never get
+    /// there.
+    fn wait_until(&mut self, time: f64) {
+        if self.scale > 0. {
+            let inner = self.base + (time - self.base_scaled) / self.scale;
+            self.clock.wait_until(inner);
+        }
+    }
+}
+
+/// Advances tweens in steps of a fixed size, however irregularly it is
+/// ticked. Time left over from a tick is kept for the next one, and
+/// `alpha` tells how far into the next step that is, for interpolating
+/// rendered state. Time is counted in whole nanoseconds, so a tick of
+/// exactly `n` steps runs exactly `n` steps.
+pub struct Driver<C> {
+    clock: C,
+    step: i64,
+    max_delta: i64,
+    accumulator: i64,
+    last: i64
+}
+
+/// The time of a clock in nanoseconds.
+#[inline]
+fn nanos(time: f64) -> i64 {
+    (time * 1e9).round() as i64
+}
+
+impl<C: Clock> Driver<C> {
+    /// A driver stepping by `step`, which must be positive. By default at
+    /// most a quarter second is simulated per tick.
+    pub fn new(clock: C, step: Duration) -> Driver<C> {
+        let step = step.num_nanoseconds().unwrap_or(0);
+        assert!(step > 0, "the step of a driver must be positive");
+        let now = nanos(clock.now());
+        Driver {
+            clock: clock,
+            step: step,
+            max_delta: 250_000_000,
+            accumulator: 0,
+            last: now
+        }
+    }
+
+    /// Limit the time simulated per tick, so a long hitch doesn't turn into
+    /// a burst of steps that causes another hitch. The time above the limit
+    /// is dropped. `max` must be positive.
+    pub fn set_max_delta(&mut self, max: Duration) {
+        let max = max.num_nanoseconds().unwrap_or(i64::MAX);
+        assert!(max > 0, "the max delta of a driver must be positive");
+        self.max_delta = max;
+    }
+
+    /// Read the clock and call `update` with the step size as often as
+    /// there are whole steps to simulate. Returns the number of steps.
+    pub fn tick(&mut self, update: |f64|) -> uint {
+        let now = nanos(self.clock.now());
+        let delta = cmp::max(cmp::min(now - self.last, self.max_delta), 0);
+        self.last = now;
+        self.accumulator += delta;
+
+        let step = self.step as f64 / 1e9;
+        let mut steps = 0;
+        while self.accumulator >= self.step {
+            update(step);
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+        steps
+    }
+
+    /// The fraction of a step that is left over, in `[0;1)`.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.accumulator as f64 / self.step as f64
+    }
+
+    /// Forget any time that has passed since the last tick, e.g. after
+    /// unpausing.
+    pub fn resync(&mut self) {
+        self.last = nanos(self.clock.now());
+        self.accumulator = 0;
+    }
+
+    pub fn clock(&mut self) -> &mut C {
+        &mut self.clock
+    }
+}
@@ -0,0 +1,252 @@
+//! Continuing a value with a release velocity and letting friction slow it
+//! down, like a flung scroll view.
+//!
+//! The whole motion is planned when the tween is created, so `remaining` is
+//! exact from the start. It consists of up to two phases: exponential
+//! decay, and if the value leaves its bounds, a critically damped spring
+//! pulling it back to the bound it crossed.
+
+use std::num::{NumCast, ToPrimitive};
+
+use {Tween, Tweenable, Access};
+use math;
+use snapshot::{Snapshot, Reader};
+use describe::Node;
+
+/// Below this speed, in units per time unit, the motion is considered
+/// to have stopped.
+static MIN_VELOCITY: f64 = 0.5;
+
+/// The spring phase ends once the value is this close to the bound.
+static SETTLE_DISTANCE: f64 = 0.01;
+
+/// A fling that slows down by friction, optionally coming to rest on one of
+/// several snap points and bouncing back off bounds.
+#[deriving(Clone)]
+pub struct Decay<T, A> {
+    acc: A,
+    start: f64,
+    velocity: f64,
+    friction: f64,
+    snap: Vec<f64>,
+    bounds: Option<(f64, f64)>,
+    stiffness: f64,
+
+    // the plan, see `plan`
+    decay_velocity: f64,
+    cross: f64,
+    bound: f64,
+    c1: f64,
+    c2: f64,
+    end: f64,
+    duration: f64,
+    current: f64
+}
+
+impl<T: Tweenable, A: Access<T>> Decay<T, A> {
+    /// Start at the current value with `velocity`, losing speed at the
+    /// exponential rate `friction` per time unit. `friction` has to be
+    /// positive.
+    pub fn new(acc: A, velocity: f64, friction: f64) -> Decay<T, A> {
+        assert!(friction > 0., "friction must be positive, got {}", friction);
+        let start = acc.get().to_f64().unwrap();
+        let mut d = Decay {
+            acc: acc,
+            start: start,
+            velocity: velocity,
+            friction: friction,
+            snap: Vec::new(),
+            bounds: None,
+            stiffness: 0.,
+            decay_velocity: velocity,
+            cross: 0.,
+            bound: 0.,
+            c1: 0.,
+            c2: 0.,
+            end: start,
+            duration: 0.,
+            current: 0.
+        };
+        d.plan();
+        d
+    }
+
+    /// Come to rest exactly on the snap point closest to where the fling
+    /// would have stopped. The velocity is adjusted to get there, arriving
+    /// on the point at the time the fling would have stopped.
+    pub fn snap(mut self, points: Vec<f64>) -> Decay<T, A> {
+        self.snap = points;
+        self.plan();
+        self
+    }
+
+    /// Bounce back off `min` and `max` with a spring. A higher `stiffness`
+    /// overshoots less and settles faster, and has to be positive.
+    pub fn bounds(mut self, min: f64, max: f64, stiffness: f64) -> Decay<T, A> {
+        assert!(stiffness > 0., "stiffness must be positive, got {}", stiffness);
+        self.bounds = Some((min, max));
+        self.stiffness = stiffness;
+        self.plan();
+        self
+    }
+
+    /// The position in the decay phase, `t` after the start.
+    #[inline]
+    fn decay_at(&self, t: f64) -> f64 {
+        let k = self.friction;
+        self.start + self.decay_velocity / k * (1. - math::exp(-k * t))
+    }
+
+    /// The position `t` after the start.
+    fn value_at(&self, t: f64) -> f64 {
+        if t >= self.duration {
+            self.end
+        } else if t < self.cross {
+            self.decay_at(t)
+        } else {
+            let s = t - self.cross;
+            self.bound + (self.c1 + self.c2 * s) * math::exp(-self.stiffness * s)
+        }
+    }
+
+    /// How fast the value is changing, in units per time unit.
+    pub fn velocity(&self) -> f64 {
+        let t = self.current;
+        if t >= self.duration {
+            0.
+        } else if t < self.cross {
+            self.decay_velocity * math::exp(-self.friction * t)
+        } else {
+            let s = t - self.cross;
+            let w = self.stiffness;
+            (self.c2 - w * (self.c1 + self.c2 * s)) * math::exp(-w * s)
+        }
+    }
+
+    /// Start the spring phase towards `bound` after `cross`, at offset `c1`
+    /// and with velocity `v`.
+    fn spring(&mut self, cross: f64, bound: f64, offset: f64, v: f64) {
+        let w = self.stiffness;
+        self.cross = cross;
+        self.bound = bound;
+        self.c1 = offset;
+        self.c2 = v + w * offset;
+        self.end = bound;
+
+        // step past the overshoot until the offset has settled
+        let step = 1. / (4. * w);
+        let mut s = 0.;
+        for _ in range(0u, 400) {
+            s += step;
+            let off = (self.c1 + self.c2 * s) * math::exp(-w * s);
+            if s * w > 1. && off.abs() < SETTLE_DISTANCE {
+                break;
+            }
+        }
+        self.duration = cross + s;
+    }
+
+    fn plan(&mut self) {
+        let k = self.friction;
+        let x0 = self.start;
+        let mut v0 = self.velocity;
+
+        let mut target = None;
+        if !self.snap.is_empty() {
+            let natural = x0 + v0 / k;
+            let mut best = self.snap[0];
+            for &p in self.snap.iter() {
+                if (p - natural).abs() < (best - natural).abs() {
+                    best = p;
+                }
+            }
+            v0 = (best - x0) * k;
+            target = Some(best);
+        }
+        self.decay_velocity = v0;
+
+        match self.bounds {
+            // released while already overscrolled
+            Some((min, _)) if x0 < min => return self.spring(0., min, x0 - min, v0),
+            Some((_, max)) if x0 > max => return self.spring(0., max, x0 - max, v0),
+            _ => {}
+        }
+
+        let stop = if v0.abs() > MIN_VELOCITY {
+            math::ln(v0.abs() / MIN_VELOCITY) / k
+        } else if target.is_some() && v0 != 0. {
+            // too slow to count as moving, but still short of the snap
+            // point, so take one time constant to get there
+            1. / k
+        } else {
+            0.
+        };
+        if target.is_some() && stop > 0. {
+            // the decay would only reach the snap point after infinite time,
+            // so go a bit faster to be on it at `stop`
+            v0 = v0 / (1. - math::exp(-k * stop));
+            self.decay_velocity = v0;
+        }
+
+        match self.bounds {
+            Some((min, max)) => {
+                let rest = self.decay_at(stop);
+                let bound = if rest < min {min} else if rest > max {max} else {rest};
+                if bound != rest {
+                    // solve decay_at(t) == bound
+                    let t = -math::ln(1. - (bound - x0) * k / v0) / k;
+                    let v = v0 * math::exp(-k * t);
+                    return self.spring(t, bound, 0., v);
+                }
+            }
+            None => {}
+        }
+
+        self.cross = stop;
+        self.duration = stop;
+        self.end = match target {
+            Some(p) => p,
+            None => self.decay_at(stop)
+        };
+    }
+}
+
+impl<T: Tweenable + 'static, A: Access<T> + 'static> Tween for Decay<T, A> {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        self.duration - self.current
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.current = 0.;
+    }
+
+    #[inline]
+    fn update(&mut self, delta: f64) -> f64 {
+        let remain = self.remaining();
+        self.current += if remain < delta {remain} else {delta};
+        let val: T = NumCast::from(self.value_at(self.current)).unwrap();
+        self.acc.set(val);
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("Decay", self.current, self.remaining())
+    }
+}
+
+/// A fling from the current value with `velocity`, slowed down by
+/// `friction`. See `Decay::snap` and `Decay::bounds` for snapping and
+/// overscroll. `friction` has to be positive.
+pub fn decay<T: Tweenable, A: Access<T>>(acc: A, velocity: f64, friction: f64) -> Decay<T, A> {
+    Decay::new(acc, velocity, friction)
+}
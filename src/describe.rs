@@ -0,0 +1,184 @@
+//! Looking into a tween tree while it plays, for debugging.
+//!
+//! `Tween::describe` returns a `Node` for the tween and each of its
+//! children, telling what kind of tween it is, how far it is and which
+//! child is playing. Walk the nodes with a `Visitor`, or render them with
+//! `text` or `dot`:
+//!
+//! ```text
+//! Repeat  elapsed 4.4  remaining inf  repeats 2
+//!   Sequence  elapsed 0.4  remaining 1.6  current 1
+//!     Single quint InOut  elapsed 0  remaining 0
+//!     Pause  elapsed 0.4  remaining 0.6
+//!     Single linear In  elapsed 0  remaining 1
+//! ```
+
+use std::fmt;
+
+/// The state of one tween in a tree.
+#[deriving(Clone, PartialEq)]
+pub struct Node {
+    /// The type of the tween, e.g. `"Sequence"`.
+    pub kind: &'static str,
+    /// The name given with `dsl::label`.
+    pub label: Option<&'static str>,
+    /// The easing and mode, for tweens that ease.
+    pub ease: Option<String>,
+    /// The time played since the last reset.
+    pub elapsed: f64,
+    pub remaining: f64,
+    /// The index of the child playing, for tweens that play them in turn.
+    pub current: Option<uint>,
+    /// How many times the tween was repeated, for `Repeat`.
+    pub repeats: Option<uint>,
+    pub children: Vec<Node>
+}
+
+impl Node {
+    pub fn new(kind: &'static str, elapsed: f64, remaining: f64) -> Node {
+        Node {
+            kind: kind,
+            label: None,
+            ease: None,
+            elapsed: elapsed,
+            remaining: remaining,
+            current: None,
+            repeats: None,
+            children: Vec::new()
+        }
+    }
+
+    pub fn label(self, label: &'static str) -> Node {
+        Node {label: Some(label), ..self}
+    }
+
+    pub fn ease(self, ease: String) -> Node {
+        Node {ease: Some(ease), ..self}
+    }
+
+    pub fn current(self, index: uint) -> Node {
+        Node {current: Some(index), ..self}
+    }
+
+    pub fn repeats(self, n: uint) -> Node {
+        Node {repeats: Some(n), ..self}
+    }
+
+    pub fn children(self, children: Vec<Node>) -> Node {
+        Node {children: children, ..self}
+    }
+
+    /// Visit this node and all below it, depth first.
+    pub fn walk(&self, v: &mut Visitor) {
+        self.walk_at(v, 0);
+    }
+
+    fn walk_at(&self, v: &mut Visitor, depth: uint) {
+        v.enter(self, depth);
+        for c in self.children.iter() {
+            c.walk_at(v, depth + 1);
+        }
+        v.leave(self, depth);
+    }
+}
+
+/// Prints a node on one line, without its children.
+impl fmt::Show for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "{}", self.kind));
+        match self.label {
+            Some(l) => try!(write!(f, " \"{}\"", l)),
+            None => {}
+        }
+        match self.ease {
+            Some(ref e) => try!(write!(f, " {}", e)),
+            None => {}
+        }
+        try!(write!(f, "  elapsed {}  remaining {}", self.elapsed, self.remaining));
+        match self.current {
+            Some(i) => try!(write!(f, "  current {}", i)),
+            None => {}
+        }
+        match self.repeats {
+            Some(n) => try!(write!(f, "  repeats {}", n)),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+/// Called for every node of a tree by `Node::walk`.
+pub trait Visitor {
+    /// Called before the children of `node`, which is `depth` levels below
+    /// the root.
+    fn enter(&mut self, node: &Node, depth: uint);
+
+    /// Called after the children of `node`.
+    fn leave(&mut self, _node: &Node, _depth: uint) {}
+}
+
+struct Text {
+    out: String
+}
+
+impl Visitor for Text {
+    fn enter(&mut self, node: &Node, depth: uint) {
+        for _ in range(0, depth) {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(format!("{}\n", node).as_slice());
+    }
+}
+
+/// Render a tree as indented text, one line per node.
+pub fn text(root: &Node) -> String {
+    let mut v = Text {out: String::new()};
+    root.walk(&mut v);
+    v.out
+}
+
+struct Dot {
+    out: String,
+    next: uint,
+    /// The ids of the nodes entered but not left, the number of their
+    /// children seen so far, and their current child.
+    stack: Vec<(uint, uint, Option<uint>)>
+}
+
+impl Visitor for Dot {
+    fn enter(&mut self, node: &Node, _depth: uint) {
+        let id = self.next;
+        self.next += 1;
+
+        let label = format!("{}", node).replace("\\", "\\\\").replace("\"", "\\\"").replace("  ", "\\n");
+        let style = if node.remaining <= 0. {", style=filled, fillcolor=gray90"} else {""};
+        self.out.push_str(format!("    n{} [label=\"{}\"{}];\n", id, label, style).as_slice());
+
+        match self.stack.last_mut() {
+            Some(&mut (parent, ref mut index, current)) => {
+                let style = if current == Some(*index) {" [style=bold]"} else {""};
+                self.out.push_str(format!("    n{} -> n{}{};\n", parent, id, style).as_slice());
+                *index += 1;
+            }
+            None => {}
+        }
+        self.stack.push((id, 0, node.current));
+    }
+
+    fn leave(&mut self, _node: &Node, _depth: uint) {
+        self.stack.pop();
+    }
+}
+
+/// Render a tree as a Graphviz graph. Finished tweens are grayed out, and
+/// the edges to current children are bold.
+pub fn dot(root: &Node) -> String {
+    let mut v = Dot {
+        out: "digraph tween {\n    node [shape=box, fontname=monospace];\n".to_string(),
+        next: 0,
+        stack: Vec::new()
+    };
+    root.walk(&mut v);
+    v.out.push_str("}\n");
+    v.out
+}
@@ -0,0 +1,121 @@
+//! A compact syntax for tween trees, via the `tween!` macro.
+//!
+//! Every node is a keyword followed by its arguments in brackets:
+//!
+//! - `seq[a, b, ...]` plays the children in order
+//! - `par[a, b, ...]` plays the children at the same time, at least one
+//! - `pause(time)` waits
+//! - `call(f)` calls the `fn()` `f`
+//! - `times(n, a)` plays `a` `n` times
+//! - `forever(a)` plays `a` over and over again, like the crate's `rep`
+//! - `label("name", a)` names the subtree `a`
+//! - `tw(expr)` is any expression returning a tween, which is boxed here
+//!
+//! ```rust
+//! let cutscene = tween!(seq[
+//!     label("enter", par[
+//!         tw(to(x.clone(), 100., quint(), InOut, 2.)),
+//!         tw(to(alpha.clone(), 1., linear(), In, 1.))
+//!     ]),
+//!     pause(0.5),
+//!     call(play_sound),
+//!     times(3, tw(to(y.clone(), 10., bounce(), Out, 0.5)))
+//! ]);
+//! ```
+//!
+//! Unknown keywords and arguments not matching the above are rejected by
+//! the compiler.
+
+use {Tween, Repeat};
+use snapshot::{Snapshot, Reader};
+use describe::Node;
+
+/// A subtree with a name. Plays exactly like the subtree.
+#[deriving(Clone)]
+pub struct Labeled {
+    name: &'static str,
+    tween: Box<Tween + 'static>
+}
+
+impl Labeled {
+    pub fn new(name: &'static str, tween: Box<Tween + 'static>) -> Labeled {
+        Labeled {name: name, tween: tween}
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl Tween for Labeled {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        self.tween.remaining()
+    }
+
+    #[inline]
+    fn done(&self) -> bool {
+        self.tween.done()
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.tween.reset();
+    }
+
+    #[inline]
+    fn update(&mut self, delta: f64) -> f64 {
+        self.tween.update(delta)
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        self.tween.save(snap);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.tween.load(snap);
+    }
+
+    fn describe(&self) -> Node {
+        self.tween.describe().label(self.name)
+    }
+}
+
+/// Returns a tween that gives a name to a given tween.
+pub fn label(name: &'static str, tween: Box<Tween + 'static>) -> Box<Tween + 'static> {
+    box Labeled::new(name, tween) as Box<Tween + 'static>
+}
+
+/// Returns a tween that plays a given tween `n` times.
+pub fn times(n: uint, tween: Box<Tween + 'static>) -> Box<Tween + 'static> {
+    box Repeat::times(tween, n) as Box<Tween + 'static>
+}
+
+/// Build a tween tree, see the module documentation for the syntax.
+#[macro_export]
+macro_rules! tween(
+    (seq [$($k:ident $a:tt),*]) => (
+        ::tween::seq(vec![$(tween!($k $a)),*])
+    );
+    (par [$($k:ident $a:tt),+]) => (
+        ::tween::par(vec![$(tween!($k $a)),*])
+    );
+    (pause ($time:expr)) => (
+        ::tween::pause($time)
+    );
+    (call ($f:expr)) => (
+        ::tween::exec($f)
+    );
+    (times ($n:expr, $k:ident $a:tt)) => (
+        ::tween::dsl::times($n, tween!($k $a))
+    );
+    (forever ($k:ident $a:tt)) => (
+        ::tween::rep(tween!($k $a))
+    );
+    (label ($name:expr, $k:ident $a:tt)) => (
+        ::tween::dsl::label($name, tween!($k $a))
+    );
+    (tw ($e:expr)) => (
+        box $e as Box<::tween::Tween + 'static>
+    )
+)
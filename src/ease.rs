@@ -1,11 +1,13 @@
-use std::f64::NAN;
-use std::f64::consts::{PI, FRAC_PI_2};
+use std::f64::consts::{PI, FRAC_PI_2, LN_2};
 
-#[deriving(Clone)]
+use math;
+
+#[deriving(Clone, PartialEq, Show)]
 pub enum Mode {
     In,
     Out,
-    InOut
+    InOut,
+    OutIn
 }
 
 /// A trait for "easing" from one value to another. Easing is an interpolation
@@ -33,16 +35,167 @@ pub trait Ease: Clone {
         }
     }
 
+    /// Ease out for the first half, and in for the second half.
+    #[inline]
+    fn ease_out_in(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            self.ease_out(2.0 * t) / 2.0
+        } else {
+            0.5 + self.ease_in(2.0 * t - 1.0) / 2.0
+        }
+    }
+
     /// Do an ease with a given `Mode`.
     #[inline]
     fn ease(&self, mode: Mode, t: f64) -> f64 {
         match mode {
             In => self.ease_in(t),
             Out => self.ease_out(t),
-            InOut => self.ease_in_out(t)
+            InOut => self.ease_in_out(t),
+            OutIn => self.ease_out_in(t)
+        }
+    }
+
+    /// The derivative of `ease_in` at `t`, by default estimated
+    /// numerically.
+    #[inline]
+    fn velocity_in(&self, t: f64) -> f64 {
+        numeric(|t| self.ease_in(t), t)
+    }
+
+    /// The derivative of `ease_out` at `t`. The default follows the default
+    /// `ease_out`, so override both or neither.
+    #[inline]
+    fn velocity_out(&self, t: f64) -> f64 {
+        self.velocity_in(1. - t)
+    }
+
+    /// The derivative of `ease_in_out` at `t`. The default follows the
+    /// default `ease_in_out`, so override both or neither.
+    #[inline]
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            self.velocity_in(2. * t)
+        } else {
+            self.velocity_out(2. * t - 1.)
+        }
+    }
+
+    /// The derivative of `ease_out_in` at `t`.
+    #[inline]
+    fn velocity_out_in(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            self.velocity_out(2. * t)
+        } else {
+            self.velocity_in(2. * t - 1.)
         }
     }
 
+    /// The derivative of the ease with a given `Mode`, i.e. how fast alpha
+    /// changes per unit of `t`.
+    #[inline]
+    fn velocity(&self, mode: Mode, t: f64) -> f64 {
+        match mode {
+            In => self.velocity_in(t),
+            Out => self.velocity_out(t),
+            InOut => self.velocity_in_out(t),
+            OutIn => self.velocity_out_in(t)
+        }
+    }
+
+    /// A short name of the easing, for debugging output.
+    fn name(&self) -> String {
+        "custom".to_string()
+    }
+}
+
+/// An object safe counterpart of `Ease`, implemented for every easing, so
+/// that differently typed easings can be stored as `Box<DynEase>`. The box
+/// is an `Ease` again, e.g. to make `Single`s of different easings have the
+/// same type.
+pub trait DynEase {
+    /// Do an ease with a given `Mode`.
+    fn dyn_ease(&self, mode: Mode, t: f64) -> f64;
+
+    /// The derivative of the ease with a given `Mode`.
+    fn dyn_velocity(&self, mode: Mode, t: f64) -> f64;
+
+    /// The name of the easing.
+    fn dyn_name(&self) -> String;
+
+    /// Same deal as `Tween::clone_into_box`.
+    fn clone_box(&self) -> Box<DynEase + 'static>;
+}
+
+impl<E: Ease + 'static> DynEase for E {
+    #[inline]
+    fn dyn_ease(&self, mode: Mode, t: f64) -> f64 {
+        self.ease(mode, t)
+    }
+
+    #[inline]
+    fn dyn_velocity(&self, mode: Mode, t: f64) -> f64 {
+        self.velocity(mode, t)
+    }
+
+    fn dyn_name(&self) -> String {
+        self.name()
+    }
+
+    fn clone_box(&self) -> Box<DynEase + 'static> {
+        box self.clone() as Box<DynEase + 'static>
+    }
+}
+
+impl Clone for Box<DynEase + 'static> {
+    #[inline]
+    fn clone(&self) -> Box<DynEase + 'static> {
+        self.clone_box()
+    }
+}
+
+impl Ease for Box<DynEase + 'static> {
+    fn ease_in(&self, t: f64) -> f64 {
+        self.dyn_ease(In, t)
+    }
+    fn ease_out(&self, t: f64) -> f64 {
+        self.dyn_ease(Out, t)
+    }
+    fn ease_in_out(&self, t: f64) -> f64 {
+        self.dyn_ease(InOut, t)
+    }
+    fn ease_out_in(&self, t: f64) -> f64 {
+        self.dyn_ease(OutIn, t)
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        self.dyn_velocity(In, t)
+    }
+    fn velocity_out(&self, t: f64) -> f64 {
+        self.dyn_velocity(Out, t)
+    }
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        self.dyn_velocity(InOut, t)
+    }
+    fn velocity_out_in(&self, t: f64) -> f64 {
+        self.dyn_velocity(OutIn, t)
+    }
+    fn name(&self) -> String {
+        self.dyn_name()
+    }
+}
+
+/// Box an easing, forgetting its type.
+pub fn boxed<E: Ease + 'static>(ease: E) -> Box<DynEase + 'static> {
+    box ease as Box<DynEase + 'static>
+}
+
+/// Estimate the derivative of `f` at `t` by central differences, one-sided
+/// at the bounds of `[0;1]`.
+pub fn numeric(f: |f64| -> f64, t: f64) -> f64 {
+    let h = 1e-6;
+    let lo = if t - h < 0. {t} else {t - h};
+    let hi = if t + h > 1. {t} else {t + h};
+    (f(hi) - f(lo)) / (hi - lo)
 }
 
 impl Ease for fn(f64) -> f64 {
@@ -64,111 +217,114 @@ impl Ease for LinearEase {
     fn ease_in_out(&self, t: f64) -> f64 {
         t
     }
+    fn velocity_in(&self, _t: f64) -> f64 {
+        1.
+    }
+    fn velocity_out(&self, _t: f64) -> f64 {
+        1.
+    }
+    fn velocity_in_out(&self, _t: f64) -> f64 {
+        1.
+    }
+    fn name(&self) -> String {
+        "linear".to_string()
+    }
 }
 
 pub fn linear() -> LinearEase {
     LinearEase
 }
 
+/// Polynomial easing, `t^p` when easing in. `quad`, `cubic`, `quart` and
+/// `quint` are the powers 2 to 5.
 #[deriving(Clone)]
-pub struct QuadEase;
+pub struct PowerEase {
+    p: f64
+}
 
-impl Ease for QuadEase {
-    fn ease_in(&self, t: f64) -> f64 {
-        t * t
-    }
-    fn ease_out(&self, t: f64) -> f64 {
-        -t * (t - 2.)
-    }
-    fn ease_in_out(&self, t: f64) -> f64 {
-        let mut t = t;
-        if {t *= 2.;t} < 1. {
-            0.5 * t * t
+impl PowerEase {
+    /// `t^p`, with a fast path for the common whole powers.
+    #[inline]
+    fn pow(&self, t: f64) -> f64 {
+        if self.p == self.p.floor() && self.p <= 16. {
+            math::powi(t, self.p as i32)
         } else {
-            -0.5 * ({t -= 1.;t} * (t - 2.) - 1.)
+            math::powf(t, self.p)
         }
     }
-}
 
-pub fn quad() -> QuadEase {
-    QuadEase
+    /// The derivative of `pow`.
+    #[inline]
+    fn dpow(&self, t: f64) -> f64 {
+        if self.p == 1. {
+            1.
+        } else if self.p == self.p.floor() && self.p <= 16. {
+            self.p * math::powi(t, self.p as i32 - 1)
+        } else {
+            self.p * math::powf(t, self.p - 1.)
+        }
+    }
 }
 
-#[deriving(Clone)]
-pub struct CubicEase;
-
-impl Ease for CubicEase {
+impl Ease for PowerEase {
     fn ease_in(&self, t: f64) -> f64 {
-        t * t * t
+        self.pow(t)
     }
     fn ease_out(&self, t: f64) -> f64 {
-        let s = t - 1.;
-        s * s * s + 1.
+        1. - self.pow(1. - t)
     }
     fn ease_in_out(&self, t: f64) -> f64 {
-        let s = t * 2.;
-        if s < 1. {
-            0.5 * s * s * s
+        if t < 0.5 {
+            0.5 * self.pow(2. * t)
         } else {
-            let u = s - 2.;
-            0.5 * (u * u * u + 2.)
+            1. - 0.5 * self.pow(2. - 2. * t)
         }
     }
-}
-
-pub fn cubic() -> CubicEase {
-    CubicEase
-}
-
-#[deriving(Clone)]
-pub struct QuartEase;
-
-impl Ease for QuartEase {
-    fn ease_in(&self, t: f64) -> f64 {
-        t * t * t * t
+    fn velocity_in(&self, t: f64) -> f64 {
+        self.dpow(t)
     }
-    fn ease_out(&self, t: f64) -> f64 {
-        let s = t - 1.;
-        -(s * s * s * s - 1.)
+    fn velocity_out(&self, t: f64) -> f64 {
+        self.dpow(1. - t)
     }
-    fn ease_in_out(&self, t: f64) -> f64 {
-        let mut t = t;
-        if {t *= 2.;t} < 1. {
-            0.5 * t * t * t * t
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            self.dpow(2. * t)
         } else {
-            -0.5 * ({t -= 2.;t} * t * t * t - 2.)
+            self.dpow(2. - 2. * t)
+        }
+    }
+    fn name(&self) -> String {
+        match self.p {
+            2. => "quad".to_string(),
+            3. => "cubic".to_string(),
+            4. => "quart".to_string(),
+            5. => "quint".to_string(),
+            p => format!("power({})", p)
         }
     }
 }
 
-pub fn quart() -> QuartEase {
-    QuartEase
+/// Easing by the power `p`, which has to be positive. A power of 1 is
+/// linear.
+pub fn power(p: f64) -> PowerEase {
+    assert!(p > 0. && p.is_finite(), "power must be positive and finite, got {}", p);
+    PowerEase {p: p}
 }
 
-#[deriving(Clone)]
-pub struct QuintEase;
+pub fn quad() -> PowerEase {
+    power(2.)
+}
 
-impl Ease for QuintEase {
-    fn ease_in(&self, t: f64) -> f64 {
-        t * t * t * t * t
-    }
-    fn ease_out(&self, t: f64) -> f64 {
-        let s = t - 1.;
-        s * s * s * s * s + 1.
-    }
-    fn ease_in_out(&self, t: f64) -> f64 {
-        let mut t = t;
+pub fn cubic() -> PowerEase {
+    power(3.)
+}
 
-        if {t *= 2.;t} < 1. {
-            0.5 * t * t * t * t * t
-        } else {
-             0.5 * ({t -= 2.;t} * t * t * t * t + 2.)
-        }
-    }
+pub fn quart() -> PowerEase {
+    power(4.)
 }
 
-pub fn quint() -> QuintEase {
-    QuintEase
+pub fn quint() -> PowerEase {
+    power(5.)
 }
 
 #[deriving(Clone)]
@@ -176,13 +332,25 @@ pub struct SineEase;
 
 impl Ease for SineEase {
     fn ease_in(&self, t: f64) -> f64 {
-        -(t * FRAC_PI_2).cos() + 1.
+        -math::cos(t * FRAC_PI_2) + 1.
     }
     fn ease_out(&self, t: f64) -> f64 {
-        (t * FRAC_PI_2).sin()
+        math::sin(t * FRAC_PI_2)
     }
     fn ease_in_out(&self, t: f64) -> f64 {
-        -0.5 * ((PI * t).cos() - 1.)
+        -0.5 * (math::cos(PI * t) - 1.)
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        FRAC_PI_2 * math::sin(t * FRAC_PI_2)
+    }
+    fn velocity_out(&self, t: f64) -> f64 {
+        FRAC_PI_2 * math::cos(t * FRAC_PI_2)
+    }
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        FRAC_PI_2 * math::sin(PI * t)
+    }
+    fn name(&self) -> String {
+        "sine".to_string()
     }
 }
 
@@ -211,32 +379,100 @@ impl Ease for CircEase {
             0.5 * ((1. - {t -= 2.;t} * t).sqrt() + 1.)
         }
     }
+    // infinite where the circle is vertical, at the bounds
+    fn velocity_in(&self, t: f64) -> f64 {
+        t / (1. - t * t).sqrt()
+    }
+    fn velocity_out(&self, t: f64) -> f64 {
+        let s = t - 1.;
+        -s / (1. - s * s).sqrt()
+    }
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        let s = t * 2.;
+        if s < 1. {
+            s / (1. - s * s).sqrt()
+        } else {
+            let u = s - 2.;
+            -u / (1. - u * u).sqrt()
+        }
+    }
+    fn name(&self) -> String {
+        "circ".to_string()
+    }
 }
 
 pub fn circ() -> CircEase {
     CircEase
 }
 
+/// A ball dropped on the floor, bouncing a number of times. By default it
+/// bounces 4 times, keeping half its speed every time.
 #[deriving(Clone)]
-pub struct BounceEase;
+pub struct BounceEase {
+    bounces: uint,
+    restitution: f64,
+    /// The length of all arcs, the first half arc being 1 long.
+    total: f64
+}
+
+impl BounceEase {
+    fn new(bounces: uint, restitution: f64) -> BounceEase {
+        let mut total = 1.;
+        let mut w = 1.;
+        for _ in range(1, bounces) {
+            w *= restitution;
+            total += 2. * w;
+        }
+        BounceEase {
+            bounces: bounces,
+            restitution: restitution,
+            total: total
+        }
+    }
+
+    /// The number of times the ball hits the floor, at least 1.
+    pub fn bounces(self, n: uint) -> BounceEase {
+        assert!(n >= 1, "a bounce needs at least one bounce");
+        BounceEase::new(n, self.restitution)
+    }
+
+    /// The fraction of its speed the ball keeps on each bounce, in `(0;1)`.
+    /// The heights of the bounces fall off with its square.
+    pub fn restitution(self, r: f64) -> BounceEase {
+        assert!(r > 0. && r < 1., "restitution must be in (0;1), got {}", r);
+        BounceEase::new(self.bounces, r)
+    }
+
+    /// Find the arc `x` is in, in units of the first half arc. Returns the
+    /// center and half width of the arc, the first one being centered on 0.
+    #[inline]
+    fn arc(&self, x: f64) -> (f64, f64) {
+        if x < 1. {
+            return (0., 1.);
+        }
+        let mut start = 1.;
+        let mut w = 1.;
+        for _ in range(1, self.bounces) {
+            w *= self.restitution;
+            if x < start + 2. * w {
+                break;
+            }
+            start += 2. * w;
+        }
+        (start + w, w)
+    }
+}
 
 impl Ease for BounceEase {
     fn ease_in(&self, t: f64) -> f64 {
         1. - self.ease_out(1. - t)
     }
     fn ease_out(&self, t: f64) -> f64 {
-        if t < 1. / 2.75 {
-            7.5625 * t * t
-        } else if t < 2. / 2.75 {
-            let s = t - 1.5 / 2.75;
-            7.5625 * s * s + 0.75
-        } else if t < 2.5/2.75 {
-            let s = t - 2.25 / 2.75;
-            7.5625 * s * s + 0.9375
-        } else {
-            let s = t - 2.625 / 2.75;
-            7.5625 * s * s + 0.984375
-        }
+        if t >= 1. {return 1.;}
+        let x = t * self.total;
+        let (c, w) = self.arc(x);
+        let s = x - c;
+        s * s + 1. - w * w
     }
     fn ease_in_out(&self, t: f64) -> f64 {
         if t < 0.5 {
@@ -245,75 +481,139 @@ impl Ease for BounceEase {
             self.ease_out(t * 2. - 1.) * 0.5 + 0.5
         }
     }
+    fn velocity_in(&self, t: f64) -> f64 {
+        self.velocity_out(1. - t)
+    }
+    fn velocity_out(&self, t: f64) -> f64 {
+        if t >= 1. {return 0.;}
+        let x = t * self.total;
+        let (c, _) = self.arc(x);
+        2. * (x - c) * self.total
+    }
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            self.velocity_in(t * 2.)
+        } else {
+            self.velocity_out(t * 2. - 1.)
+        }
+    }
+    fn name(&self) -> String {
+        format!("bounce({}, {})", self.bounces, self.restitution)
+    }
 }
 
 pub fn bounce() -> BounceEase {
-    BounceEase
+    BounceEase::new(4, 0.5)
 }
 
+/// A spring overshooting and oscillating around the target, decaying
+/// exponentially.
 #[deriving(Clone)]
 pub struct ElasticEase {
-    a: f64,
-    p: f64
+    amplitude: f64,
+    /// `None` uses 0.3, or 0.45 for `InOut`.
+    period: Option<f64>
+}
+
+impl ElasticEase {
+    /// How far the first oscillation overshoots, at least 1.
+    pub fn amplitude(self, a: f64) -> ElasticEase {
+        assert!(a >= 1. && a.is_finite(), "amplitude must be at least 1, got {}", a);
+        ElasticEase {amplitude: a, ..self}
+    }
+
+    /// The length of one oscillation, as a fraction of the duration.
+    pub fn period(self, p: f64) -> ElasticEase {
+        assert!(p > 0. && p.is_finite(), "period must be positive, got {}", p);
+        ElasticEase {period: Some(p), ..self}
+    }
+
+    /// Amplitude, period and phase shift, with `p` as the default period.
+    #[inline]
+    fn params(&self, p: f64) -> (f64, f64, f64) {
+        let p = self.period.unwrap_or(p);
+        let a = self.amplitude;
+        (a, p, p / (2. * PI) * math::asin(1. / a))
+    }
 }
 
 impl Ease for ElasticEase {
     fn ease_in(&self, t: f64) -> f64 {
-        let mut t = t;
-        let p = if self.p.is_nan() {0.3} else {self.p};
-        let a = if self.a.is_nan() || self.a < 1. {1.} else {self.a};
+        let (a, p, s) = self.params(0.3);
         if t == 0. {return 0.;}
         if t == 1. {return 1.;}
-
-        let s = if self.a.is_nan() || self.a < 1. {p / 4.} else {
-            p / (2. * PI) * (1. / a).asin()
-        };
-
-        -(a * 2.0f64.powf(10. * {t -= 1.;t}) * ((t - s) * (2. * PI) / p).sin())
+        let u = t - 1.;
+        -(a * math::powf(2., 10. * u) * math::sin((u - s) * (2. * PI) / p))
     }
     fn ease_out(&self, t: f64) -> f64 {
-        let p = if self.p.is_nan() {0.3} else {self.p};
-        let a = if self.a.is_nan() || self.a < 1. {1.} else {self.a};
+        let (a, p, s) = self.params(0.3);
         if t == 0. {return 0.;}
         if t == 1. {return 1.;}
-
-        let s = if self.a.is_nan() || self.a < 1. {p / 4.} else {
-            p / (2. * PI) * (1. / a).asin()
-        };
-
-        a * 2.0f64.powf(-10. * t) * ((t - s) * (2. * PI) / p).sin() + 1.
+        a * math::powf(2., -10. * t) * math::sin((t - s) * (2. * PI) / p) + 1.
     }
     fn ease_in_out(&self, t: f64) -> f64 {
-        let mut t = t;
-        let p = if self.p.is_nan() {0.3 * 1.5} else {self.p};
-        let a = if self.a.is_nan() || self.a < 1. {1.} else {self.a};
+        let (a, p, s) = self.params(0.3 * 1.5);
         if t == 0. {return 0.;}
-        if {t *= 2.;t} == 2. {return 1.;}
-
-        let s = if self.a.is_nan() || self.a < 1. {p / 4.} else {
-            p / (2. * PI) * (1. / a).asin()
-        };
-
-        if t < 1. {
-            -0.5 * (a * 2.0f64.powf(10. * {t -= 1.;t}) * ((t - s) * (2. * PI) / p).sin())
+        if t == 1. {return 1.;}
+        let u = t * 2. - 1.;
+        if u < 0. {
+            -0.5 * (a * math::powf(2., 10. * u) * math::sin((u - s) * (2. * PI) / p))
         } else {
-            a * 2.0f64.powf(-10. * {t -= 1.;t}) * ((t - s) * (2. * PI) / p).sin() * 0.5 + 1.
+            a * math::powf(2., -10. * u) * math::sin((u - s) * (2. * PI) / p) * 0.5 + 1.
+        }
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        let (a, p, s) = self.params(0.3);
+        let w = 2. * PI / p;
+        let u = t - 1.;
+        -a * math::powf(2., 10. * u) * (10. * LN_2 * math::sin(w * (u - s)) + w * math::cos(w * (u - s)))
+    }
+    fn velocity_out(&self, t: f64) -> f64 {
+        let (a, p, s) = self.params(0.3);
+        let w = 2. * PI / p;
+        a * math::powf(2., -10. * t) * (-10. * LN_2 * math::sin(w * (t - s)) + w * math::cos(w * (t - s)))
+    }
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        let (a, p, s) = self.params(0.3 * 1.5);
+        let w = 2. * PI / p;
+        let u = t * 2. - 1.;
+        if u < 0. {
+            -a * math::powf(2., 10. * u) * (10. * LN_2 * math::sin(w * (u - s)) + w * math::cos(w * (u - s)))
+        } else {
+            a * math::powf(2., -10. * u) * (-10. * LN_2 * math::sin(w * (u - s)) + w * math::cos(w * (u - s)))
+        }
+    }
+    fn name(&self) -> String {
+        match self.period {
+            Some(p) => format!("elastic({}, {})", self.amplitude, p),
+            None => format!("elastic({})", self.amplitude)
         }
     }
 }
 
 pub fn elastic() -> ElasticEase {
     ElasticEase {
-        a: NAN,
-        p: NAN
+        amplitude: 1.,
+        period: None
     }
 }
 
+/// Pulls back before moving towards the target, and overshoots it when
+/// easing out.
 #[deriving(Clone)]
 pub struct BackEase {
     s: f64
 }
 
+impl BackEase {
+    /// How far to pull back, 0 being no overshoot at all. The default of
+    /// 1.70158 overshoots by 10%.
+    pub fn overshoot(self, s: f64) -> BackEase {
+        assert!(s >= 0. && s.is_finite(), "overshoot must not be negative, got {}", s);
+        BackEase {s: s}
+    }
+}
+
 impl Ease for BackEase {
     fn ease_in(&self, t: f64) -> f64 {
         let s = self.s;
@@ -336,6 +636,28 @@ impl Ease for BackEase {
             0.5 * (r * r * ((q + 1.) * r + q) + 2.)
         }
     }
+    fn velocity_in(&self, t: f64) -> f64 {
+        let s = self.s;
+        3. * (s + 1.) * t * t - 2. * s * t
+    }
+    fn velocity_out(&self, t: f64) -> f64 {
+        let s = self.s;
+        let u = t - 1.;
+        3. * (s + 1.) * u * u + 2. * s * u
+    }
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        let q = self.s * 1.525;
+        let u = t * 2.;
+        if u < 1. {
+            3. * (q + 1.) * u * u - 2. * q * u
+        } else {
+            let r = u - 2.;
+            3. * (q + 1.) * r * r + 2. * q * r
+        }
+    }
+    fn name(&self) -> String {
+        format!("back({})", self.s)
+    }
 }
 
 pub fn back() -> BackEase {
@@ -343,3 +665,343 @@ pub fn back() -> BackEase {
         s: 1.70158
     }
 }
+
+/// A cubic bezier curve through `(0, 0)`, `(x1, y1)`, `(x2, y2)` and `(1, 1)`,
+/// as used by CSS `cubic-bezier()` and After Effects keyframe handles.
+#[deriving(Clone)]
+pub struct BezierEase {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64
+}
+
+impl BezierEase {
+    /// One coordinate of the curve at parameter `u`, given its two control
+    /// values. The end points are fixed at 0 and 1.
+    #[inline]
+    fn sample(a1: f64, a2: f64, u: f64) -> f64 {
+        let v = 1. - u;
+        3. * v * v * u * a1 + 3. * v * u * u * a2 + u * u * u
+    }
+
+    /// Derivative of `sample` with respect to `u`.
+    #[inline]
+    fn slope(a1: f64, a2: f64, u: f64) -> f64 {
+        let v = 1. - u;
+        3. * v * v * a1 + 6. * v * u * (a2 - a1) + 3. * u * u * (1. - a2)
+    }
+
+    /// Derivative of `slope` with respect to `u`.
+    #[inline]
+    fn curvature(a1: f64, a2: f64, u: f64) -> f64 {
+        let v = 1. - u;
+        -6. * v * a1 + 6. * (v - u) * (a2 - a1) + 6. * u * (1. - a2)
+    }
+
+    /// Find the curve parameter `u` at which the x coordinate equals `x`.
+    /// Newton's method usually converges in a few steps, bisection is the
+    /// fallback for flat regions.
+    fn solve(&self, x: f64) -> f64 {
+        let mut u = x;
+        for _ in range(0u, 8) {
+            let err = BezierEase::sample(self.x1, self.x2, u) - x;
+            if err.abs() < 1e-7 {
+                return u;
+            }
+            let d = BezierEase::slope(self.x1, self.x2, u);
+            if d.abs() < 1e-6 {
+                break;
+            }
+            u -= err / d;
+        }
+
+        let mut lo = 0f64;
+        let mut hi = 1f64;
+        u = x;
+        for _ in range(0u, 32) {
+            let cur = BezierEase::sample(self.x1, self.x2, u);
+            if (cur - x).abs() < 1e-7 {
+                break;
+            }
+            if cur < x { lo = u; } else { hi = u; }
+            u = (lo + hi) / 2.;
+        }
+        u
+    }
+}
+
+impl Ease for BezierEase {
+    fn ease_in(&self, t: f64) -> f64 {
+        if t <= 0. {return 0.;}
+        if t >= 1. {return 1.;}
+        BezierEase::sample(self.y1, self.y2, self.solve(t))
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        let u = self.solve(t.max(0.).min(1.));
+        let dx = BezierEase::slope(self.x1, self.x2, u);
+        let dy = BezierEase::slope(self.y1, self.y2, u);
+        if dx.abs() > 1e-9 || dy.abs() > 1e-9 {
+            return dy / dx;
+        }
+        // both stand still where a control point sits on its end point,
+        // like at the start of CSS `ease-out`, so take the limit from the
+        // second derivatives, or estimate it if those vanish as well
+        let ddx = BezierEase::curvature(self.x1, self.x2, u);
+        let ddy = BezierEase::curvature(self.y1, self.y2, u);
+        if ddx.abs() > 1e-9 {
+            ddy / ddx
+        } else {
+            numeric(|t| self.ease_in(t), t)
+        }
+    }
+    fn velocity_out(&self, t: f64) -> f64 {
+        self.velocity_in(1. - t)
+    }
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            self.velocity_in(2. * t)
+        } else {
+            self.velocity_out(2. * t - 1.)
+        }
+    }
+    fn name(&self) -> String {
+        format!("bezier({}, {}, {}, {})", self.x1, self.y1, self.x2, self.y2)
+    }
+}
+
+/// A cubic bezier easing. `x1` and `x2` are clamped to `[0;1]` so the curve
+/// stays a function of time.
+pub fn bezier(x1: f64, y1: f64, x2: f64, y2: f64) -> BezierEase {
+    BezierEase {
+        x1: x1.max(0.).min(1.),
+        y1: y1,
+        x2: x2.max(0.).min(1.),
+        y2: y2
+    }
+}
+
+/// Jumps between `steps` evenly spaced levels instead of moving smoothly,
+/// like CSS `steps()`. By default a step is taken at the end of every
+/// interval.
+#[deriving(Clone)]
+pub struct StepsEase {
+    steps: uint,
+    start: bool
+}
+
+impl StepsEase {
+    /// Take the steps at the start of every interval instead, so the first
+    /// jump is right away.
+    pub fn start(self) -> StepsEase {
+        StepsEase {start: true, ..self}
+    }
+}
+
+impl Ease for StepsEase {
+    fn ease_in(&self, t: f64) -> f64 {
+        let n = self.steps as f64;
+        if self.start {
+            if t <= 0. {0.} else {((t * n).ceil() / n).min(1.)}
+        } else {
+            if t >= 1. {1.} else {((t * n).floor() / n).max(0.)}
+        }
+    }
+    // flat between the jumps
+    fn velocity_in(&self, _t: f64) -> f64 {
+        0.
+    }
+    fn velocity_out(&self, _t: f64) -> f64 {
+        0.
+    }
+    fn velocity_in_out(&self, _t: f64) -> f64 {
+        0.
+    }
+    fn name(&self) -> String {
+        if self.start {
+            format!("steps({}, start)", self.steps)
+        } else {
+            format!("steps({})", self.steps)
+        }
+    }
+}
+
+/// An easing in `n` steps, at least 1.
+pub fn steps(n: uint) -> StepsEase {
+    assert!(n >= 1, "steps need at least one step");
+    StepsEase {steps: n, start: false}
+}
+
+/// Plays an easing backwards in time, from 1 to 0.
+#[deriving(Clone)]
+pub struct Reversed<E> {
+    ease: E
+}
+
+impl<E: Ease> Ease for Reversed<E> {
+    fn ease_in(&self, t: f64) -> f64 {
+        self.ease.ease_in(1. - t)
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        -self.ease.velocity_in(1. - t)
+    }
+    fn name(&self) -> String {
+        format!("reverse({})", self.ease.name())
+    }
+}
+
+/// Reverse an easing in time, so it goes from 1 to 0.
+pub fn reverse<E: Ease>(ease: E) -> Reversed<E> {
+    Reversed {ease: ease}
+}
+
+/// Plays an easing forwards in the first half and backwards in the second,
+/// going from 0 to 1 and back to 0.
+#[deriving(Clone)]
+pub struct Mirrored<E> {
+    ease: E
+}
+
+impl<E: Ease> Ease for Mirrored<E> {
+    fn ease_in(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            self.ease.ease_in(2. * t)
+        } else {
+            self.ease.ease_in(2. - 2. * t)
+        }
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        if t < 0.5 {
+            2. * self.ease.velocity_in(2. * t)
+        } else {
+            -2. * self.ease.velocity_in(2. - 2. * t)
+        }
+    }
+    fn name(&self) -> String {
+        format!("mirror({})", self.ease.name())
+    }
+}
+
+/// Mirror an easing to make a yoyo curve.
+pub fn mirror<E: Ease>(ease: E) -> Mirrored<E> {
+    Mirrored {ease: ease}
+}
+
+/// One easing followed by another, meeting at `split`.
+#[deriving(Clone)]
+pub struct Chained<A, B> {
+    first: A,
+    second: B,
+    split: f64
+}
+
+impl<A: Ease, B: Ease> Ease for Chained<A, B> {
+    fn ease_in(&self, t: f64) -> f64 {
+        let s = self.split;
+        if t < s {
+            self.first.ease_in(t / s) * s
+        } else {
+            s + self.second.ease_in((t - s) / (1. - s)) * (1. - s)
+        }
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        let s = self.split;
+        if t < s {
+            self.first.velocity_in(t / s)
+        } else {
+            self.second.velocity_in((t - s) / (1. - s))
+        }
+    }
+    fn name(&self) -> String {
+        format!("chain({}, {}, {})", self.first.name(), self.second.name(), self.split)
+    }
+}
+
+/// Concatenate two easings. `first` covers `t` and alpha in `[0;split]`,
+/// `second` the rest. `split` is clamped to `(0;1)`.
+pub fn chain<A: Ease, B: Ease>(first: A, second: B, split: f64) -> Chained<A, B> {
+    Chained {
+        first: first,
+        second: second,
+        split: split.max(1e-9).min(1. - 1e-9)
+    }
+}
+
+/// A weighted average of two easings.
+#[deriving(Clone)]
+pub struct Mixed<A, B> {
+    a: A,
+    b: B,
+    weight: f64
+}
+
+impl<A: Ease, B: Ease> Ease for Mixed<A, B> {
+    fn ease_in(&self, t: f64) -> f64 {
+        self.a.ease_in(t) * (1. - self.weight) + self.b.ease_in(t) * self.weight
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        self.a.velocity_in(t) * (1. - self.weight) + self.b.velocity_in(t) * self.weight
+    }
+    fn name(&self) -> String {
+        format!("mix({}, {}, {})", self.a.name(), self.b.name(), self.weight)
+    }
+}
+
+/// Mix two easings, a `weight` of 0 is all `a`, 1 is all `b`. `weight` has
+/// to be in `[0;1]`.
+pub fn mix<A: Ease, B: Ease>(a: A, b: B, weight: f64) -> Mixed<A, B> {
+    assert!(weight >= 0. && weight <= 1., "mix weight must be in [0;1], got {}", weight);
+    Mixed {a: a, b: b, weight: weight}
+}
+
+/// An easing with its time remapped by a power curve.
+#[deriving(Clone)]
+pub struct Gamma<E> {
+    ease: E,
+    gamma: f64
+}
+
+impl<E: Ease> Ease for Gamma<E> {
+    fn ease_in(&self, t: f64) -> f64 {
+        self.ease.ease_in(math::powf(t.max(0.), self.gamma))
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        let t = t.max(0.);
+        self.ease.velocity_in(math::powf(t, self.gamma)) * self.gamma * math::powf(t, self.gamma - 1.)
+    }
+    fn name(&self) -> String {
+        format!("gamma({}, {})", self.ease.name(), self.gamma)
+    }
+}
+
+/// Remap time by `t^gamma` before easing. A `gamma` above 1 makes the
+/// start of the curve slower, below 1 faster. `gamma` has to be positive
+/// and finite.
+pub fn gamma<E: Ease>(ease: E, gamma: f64) -> Gamma<E> {
+    assert!(gamma > 0. && gamma.is_finite(), "gamma must be positive and finite, got {}", gamma);
+    Gamma {ease: ease, gamma: gamma}
+}
+
+/// An easing that never leaves `[0;1]`.
+#[deriving(Clone)]
+pub struct Clamped<E> {
+    ease: E
+}
+
+impl<E: Ease> Ease for Clamped<E> {
+    fn ease_in(&self, t: f64) -> f64 {
+        self.ease.ease_in(t).max(0.).min(1.)
+    }
+    fn velocity_in(&self, t: f64) -> f64 {
+        let a = self.ease.ease_in(t);
+        if a < 0. || a > 1. {0.} else {self.ease.velocity_in(t)}
+    }
+    fn name(&self) -> String {
+        format!("clamp({})", self.ease.name())
+    }
+}
+
+/// Cut off the overshoot of curves like `back()` and `elastic()`.
+pub fn clamp<E: Ease>(ease: E) -> Clamped<E> {
+    Clamped {ease: ease}
+}
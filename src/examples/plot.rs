@@ -0,0 +1,490 @@
+//! `tween-plot`: draws easing curves in a grid, as PPM, PNG, SVG, CSV or
+//! right in the terminal.
+//!
+//! ```text
+//! tween-plot -m all -o easings.svg quint elastic "cubic-bezier(.17,.67,.83,.67)"
+//! tween-plot "steps(4)" easeOutBounce
+//! ```
+//!
+//! Easings are looked up by name with `tween::registry`, so everything it
+//! understands works here, including parameters like `power(2.5)`. The
+//! lines at alpha 0 and 1 are drawn in every cell, and where a curve
+//! overshoots them the background is tinted. The raster formats have no
+//! text, the cells are in the order of the easings and modes given.
+
+extern crate getopts;
+extern crate tween;
+
+use std::f64::INFINITY;
+use std::io;
+use std::io::File;
+use std::os;
+
+use getopts::{optopt, optflag, getopts, usage, OptGroup};
+
+use tween::ease::{Ease, DynEase, Mode, In, Out, InOut, OutIn};
+use tween::registry::Registry;
+
+/// One curve of the grid.
+struct Cell {
+    title: String,
+    /// `(t, alpha)` pairs, `t` going from 0 to 1.
+    samples: Vec<(f64, f64)>,
+    /// The lowest and highest alpha sampled, to find overshoots.
+    min: f64,
+    max: f64,
+    /// The range of alpha shown, at least `[0;1]`.
+    lo: f64,
+    hi: f64
+}
+
+impl Cell {
+    fn new(title: String, ease: &Box<DynEase + 'static>, mode: Mode, samples: uint) -> Cell {
+        let samples: Vec<(f64, f64)> = range(0, samples + 1).map(|i| {
+            let t = i as f64 / samples as f64;
+            (t, ease.ease(mode.clone(), t))
+        }).collect();
+        let min = samples.iter().fold(INFINITY, |a, &(_, v)| a.min(v));
+        let max = samples.iter().fold(-INFINITY, |a, &(_, v)| a.max(v));
+        let (lo, hi) = (min.min(0.), max.max(1.));
+        // leave some room above and below
+        let pad = (hi - lo) * 0.05;
+        Cell {title: title, samples: samples, min: min, max: max, lo: lo - pad, hi: hi + pad}
+    }
+
+    /// The fraction of the cell height from the top at `alpha`.
+    fn y(&self, alpha: f64) -> f64 {
+        (self.hi - alpha) / (self.hi - self.lo)
+    }
+}
+
+/// The layout of the grid.
+struct Grid {
+    cells: Vec<Cell>,
+    columns: uint,
+    /// The size of one cell, in pixels or characters.
+    width: uint,
+    height: uint
+}
+
+impl Grid {
+    fn rows(&self) -> uint {
+        (self.cells.len() + self.columns - 1) / self.columns
+    }
+}
+
+#[deriving(Clone)]
+struct Rgb(u8, u8, u8);
+
+static WHITE: Rgb = Rgb(255, 255, 255);
+static FRAME: Rgb = Rgb(210, 210, 210);
+static AXIS: Rgb = Rgb(150, 150, 150);
+static OVERSHOOT: Rgb = Rgb(253, 224, 224);
+static CURVE: Rgb = Rgb(0, 0, 255);
+
+/// Room around the plot in every cell, in pixels.
+static MARGIN: uint = 10;
+
+/// An RGB image.
+struct Image {
+    width: uint,
+    height: uint,
+    data: Vec<Rgb>
+}
+
+impl Image {
+    fn new(width: uint, height: uint) -> Image {
+        Image {width: width, height: height, data: Vec::from_elem(width * height, WHITE)}
+    }
+
+    fn set(&mut self, x: int, y: int, col: Rgb) {
+        if x >= 0 && y >= 0 && (x as uint) < self.width && (y as uint) < self.height {
+            self.data[y as uint * self.width + x as uint] = col;
+        }
+    }
+
+    fn hline(&mut self, x0: int, x1: int, y: int, col: Rgb) {
+        for x in range(x0, x1 + 1) {
+            self.set(x, y, col.clone());
+        }
+    }
+
+    fn vline(&mut self, x: int, y0: int, y1: int, col: Rgb) {
+        for y in range(y0, y1 + 1) {
+            self.set(x, y, col.clone());
+        }
+    }
+
+    fn fill(&mut self, x0: int, y0: int, x1: int, y1: int, col: Rgb) {
+        for y in range(y0, y1 + 1) {
+            self.hline(x0, x1, y, col.clone());
+        }
+    }
+
+    /// Bresenham's line.
+    fn line(&mut self, x0: int, y0: int, x1: int, y1: int, col: Rgb) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = (if x0 < x1 {1} else {-1}, if y0 < y1 {1} else {-1});
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        loop {
+            self.set(x, y, col.clone());
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy { err += dy; x += sx; }
+            if e2 <= dx { err += dx; y += sy; }
+        }
+    }
+
+    fn rgb(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.data.len() * 3);
+        for &Rgb(r, g, b) in self.data.iter() {
+            out.push(r);
+            out.push(g);
+            out.push(b);
+        }
+        out
+    }
+}
+
+fn rasterize(grid: &Grid) -> Image {
+    let (cw, ch) = (grid.width, grid.height);
+    let mut img = Image::new(cw * grid.columns, ch * grid.rows());
+    for (i, cell) in grid.cells.iter().enumerate() {
+        let (ox, oy) = ((i % grid.columns * cw) as int, (i / grid.columns * ch) as int);
+        let (w, h) = ((cw - 2 * MARGIN) as f64, (ch - 2 * MARGIN) as f64);
+        let px = |t: f64| ox + MARGIN as int + (t * w).round() as int;
+        let py = |a: f64| oy + MARGIN as int + (cell.y(a) * h).round() as int;
+
+        // overshoot regions, the frame and the lines at alpha 0 and 1
+        let (left, right) = (px(0.), px(1.));
+        if cell.max > 1. {
+            img.fill(left, py(cell.hi), right, py(1.), OVERSHOOT);
+        }
+        if cell.min < 0. {
+            img.fill(left, py(0.), right, py(cell.lo), OVERSHOOT);
+        }
+        img.hline(left, right, py(cell.hi), FRAME);
+        img.hline(left, right, py(cell.lo), FRAME);
+        img.vline(left, py(cell.hi), py(cell.lo), AXIS);
+        img.vline(right, py(cell.hi), py(cell.lo), FRAME);
+        img.hline(left, right, py(0.), AXIS);
+        img.hline(left, right, py(1.), AXIS);
+
+        for pair in cell.samples.windows(2) {
+            let ((t0, a0), (t1, a1)) = (pair[0], pair[1]);
+            img.line(px(t0), py(a0), px(t1), py(a1), CURVE);
+        }
+    }
+    img
+}
+
+fn write_ppm(img: &Image, out: &mut Writer) -> io::IoResult<()> {
+    try!(write!(out, "P6 {} {} 255\n", img.width, img.height));
+    out.write(img.rgb().as_slice())
+}
+
+/// A table for the CRC-32 of PNG chunks.
+fn crc_table() -> Vec<u32> {
+    range(0u32, 256).map(|n| {
+        let mut c = n;
+        for _ in range(0u, 8) {
+            c = if c & 1 == 1 {0xedb88320 ^ (c >> 1)} else {c >> 1};
+        }
+        c
+    }).collect()
+}
+
+fn push_be(buf: &mut Vec<u8>, n: u32) {
+    buf.push((n >> 24) as u8);
+    buf.push((n >> 16) as u8);
+    buf.push((n >> 8) as u8);
+    buf.push(n as u8);
+}
+
+fn write_chunk(out: &mut Writer, table: &[u32], kind: &[u8], data: &[u8]) -> io::IoResult<()> {
+    try!(out.write_be_u32(data.len() as u32));
+    let mut crc = 0xffffffffu32;
+    for &b in kind.iter().chain(data.iter()) {
+        crc = table[((crc ^ b as u32) & 0xff) as uint] ^ (crc >> 8);
+    }
+    try!(out.write(kind));
+    try!(out.write(data));
+    out.write_be_u32(crc ^ 0xffffffff)
+}
+
+/// A PNG without compression, so it needs nothing but this file. The
+/// images are small anyway.
+fn write_png(img: &Image, out: &mut Writer) -> io::IoResult<()> {
+    let table = crc_table();
+    try!(out.write(&[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]));
+
+    let mut header = Vec::new();
+    push_be(&mut header, img.width as u32);
+    push_be(&mut header, img.height as u32);
+    // 8 bit RGB, no interlacing
+    header.push_all(&[8, 2, 0, 0, 0]);
+    try!(write_chunk(out, table.as_slice(), b"IHDR", header.as_slice()));
+
+    // every scanline starts with filter type 0
+    let rgb = img.rgb();
+    let mut raw = Vec::with_capacity(rgb.len() + img.height);
+    for line in rgb.as_slice().chunks(img.width * 3) {
+        raw.push(0u8);
+        raw.push_all(line);
+    }
+
+    // a zlib stream of stored deflate blocks
+    let mut z = vec![0x78u8, 0x01];
+    let blocks: Vec<&[u8]> = raw.as_slice().chunks(65535).collect();
+    for (i, block) in blocks.iter().enumerate() {
+        let len = block.len() as u16;
+        z.push(if i + 1 == blocks.len() {1} else {0});
+        z.push_all(&[len as u8, (len >> 8) as u8, !len as u8, (!len >> 8) as u8]);
+        z.push_all(*block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in raw.iter() {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    push_be(&mut z, (b << 16) | a);
+    try!(write_chunk(out, table.as_slice(), b"IDAT", z.as_slice()));
+    write_chunk(out, table.as_slice(), b"IEND", &[])
+}
+
+fn escape(s: &str) -> String {
+    s.replace("&", "&amp;").replace("<", "&lt;").replace(">", "&gt;")
+}
+
+fn write_svg(grid: &Grid, out: &mut Writer) -> io::IoResult<()> {
+    let (cw, ch) = (grid.width as f64, grid.height as f64);
+    let m = MARGIN as f64;
+    let top = 16.; // room for the title
+    try!(write!(out, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+                      font-family=\"monospace\" font-size=\"11\">\n\
+                      <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n",
+                cw * grid.columns as f64, ch * grid.rows() as f64));
+    for (i, cell) in grid.cells.iter().enumerate() {
+        let ox = (i % grid.columns) as f64 * cw;
+        let oy = (i / grid.columns) as f64 * ch;
+        let (w, h) = (cw - 2. * m - 14., ch - 2. * m - top);
+        let x0 = ox + m + 14.; // room for the alpha labels
+        let px = |t: f64| x0 + t * w;
+        let py = |a: f64| oy + m + top + cell.y(a) * h;
+
+        try!(write!(out, "<text x=\"{}\" y=\"{}\">{}</text>\n", ox + m, oy + m + 8., escape(cell.title.as_slice())));
+        if cell.max > 1. {
+            try!(write!(out, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#fde0e0\"/>\n",
+                        px(0.), py(cell.hi), w, py(1.) - py(cell.hi)));
+        }
+        if cell.min < 0. {
+            try!(write!(out, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#fde0e0\"/>\n",
+                        px(0.), py(0.), w, py(cell.lo) - py(0.)));
+        }
+        try!(write!(out, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#d2d2d2\"/>\n",
+                    px(0.), py(cell.hi), w, h));
+        for &a in [0f64, 1.].iter() {
+            try!(write!(out, "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#969696\"/>\n",
+                        px(0.), py(a), px(1.), py(a)));
+            try!(write!(out, "<text x=\"{}\" y=\"{}\" text-anchor=\"end\" fill=\"#969696\">{}</text>\n",
+                        px(0.) - 3., py(a) + 4., a));
+        }
+        let points: Vec<String> = cell.samples.iter()
+            .map(|&(t, a)| format!("{:.2},{:.2}", px(t), py(a)))
+            .collect();
+        try!(write!(out, "<polyline points=\"{}\" fill=\"none\" stroke=\"#0000ff\" stroke-width=\"1.5\"/>\n",
+                    points.connect(" ")));
+    }
+    write!(out, "</svg>\n")
+}
+
+/// Curves drawn with characters, for a quick look in the terminal.
+fn write_ascii(grid: &Grid, out: &mut Writer) -> io::IoResult<()> {
+    let (cw, ch) = (grid.width, grid.height);
+    for row in grid.cells.as_slice().chunks(grid.columns) {
+        // title, then the plot area of every cell, side by side
+        let mut lines: Vec<Vec<char>> = Vec::from_fn(ch + 1, |_| Vec::from_elem(row.len() * (cw + 8), ' '));
+        for (i, cell) in row.iter().enumerate() {
+            let ox = i * (cw + 8);
+            for (j, c) in cell.title.as_slice().chars().take(cw + 6).enumerate() {
+                lines[0][ox + j] = c;
+            }
+            let py = |a: f64| 1 + ((cell.y(a) * (ch - 1) as f64).round() as uint).min(ch - 1);
+            let (zero, one) = (py(0.), py(1.));
+            for y in range(1, ch + 1) {
+                let over = (y < one && cell.max > 1.) || (y > zero && cell.min < 0.);
+                let fill = if over {'.'} else {' '};
+                let label = if y == one {" 1 |"} else if y == zero {" 0 |"} else {"   |"};
+                for (j, c) in label.chars().enumerate() {
+                    lines[y][ox + j] = c;
+                }
+                for x in range(0, cw) {
+                    lines[y][ox + 4 + x] = if y == one || y == zero {'-'} else {fill};
+                }
+            }
+            for &(t, a) in cell.samples.iter() {
+                let x = ((t * (cw - 1) as f64).round() as uint).min(cw - 1);
+                lines[py(a)][ox + 4 + x] = '*';
+            }
+        }
+        for l in lines.iter() {
+            let s: String = l.iter().map(|&c| c).collect();
+            try!(out.write_line(s.as_slice().trim_right()));
+        }
+        try!(out.write_line(""));
+    }
+    Ok(())
+}
+
+/// One row per sample, one column per curve.
+fn write_csv(grid: &Grid, out: &mut Writer) -> io::IoResult<()> {
+    let titles: Vec<String> = grid.cells.iter()
+        .map(|c| format!("\"{}\"", c.title.replace("\"", "\"\"")))
+        .collect();
+    try!(write!(out, "t,{}\n", titles.connect(",")));
+    let n = grid.cells[0].samples.len();
+    for i in range(0, n) {
+        let (t, _) = grid.cells[0].samples[i];
+        let vals: Vec<String> = grid.cells.iter().map(|c| { let (_, a) = c.samples[i]; a.to_string() }).collect();
+        try!(write!(out, "{},{}\n", t, vals.connect(",")));
+    }
+    Ok(())
+}
+
+fn parse_mode(s: &str) -> Option<Vec<Mode>> {
+    match s {
+        "in" => Some(vec![In]),
+        "out" => Some(vec![Out]),
+        "inout" => Some(vec![InOut]),
+        "outin" => Some(vec![OutIn]),
+        "all" => Some(vec![In, Out, InOut, OutIn]),
+        _ => None
+    }
+}
+
+/// `pixels` is for the formats with a margin around every cell.
+fn parse_size(s: &str, pixels: bool) -> Option<(uint, uint)> {
+    let min = if pixels {2 * MARGIN} else {0};
+    let parts: Vec<Option<uint>> = s.split('x').map(|p| from_str::<uint>(p)).collect();
+    match parts.as_slice() {
+        [Some(w), Some(h)] if w > min && h > min => Some((w, h)),
+        _ => None
+    }
+}
+
+fn opts() -> Vec<OptGroup> {
+    vec![
+        optopt("m", "mode", "in, out, inout or outin, or all for one cell each", "MODE"),
+        optopt("f", "format", "ppm, png, svg, ascii or csv, by default from the output name, else ascii", "FORMAT"),
+        optopt("o", "output", "the file to write, standard output if missing", "FILE"),
+        optopt("s", "size", "the size of one cell, in pixels or characters for ascii", "WxH"),
+        optopt("n", "samples", "samples per curve, by default one per pixel or character", "N"),
+        optopt("c", "columns", "cells per row, 4 by default", "N"),
+        optflag("h", "help", "show this help")
+    ]
+}
+
+fn fail(msg: String) {
+    let _ = writeln!(&mut io::stderr(), "tween-plot: {}", msg);
+    os::set_exit_status(1);
+}
+
+fn main() {
+    let args = os::args();
+    let opts = opts();
+    let m = match getopts(args.tail(), opts.as_slice()) {
+        Ok(m) => m,
+        Err(e) => return fail(e.to_string())
+    };
+    if m.opt_present("h") || m.free.is_empty() {
+        println!("{}", usage("Usage: tween-plot [options] EASING...", opts.as_slice()));
+        return;
+    }
+
+    let output = m.opt_str("o");
+    let format = match m.opt_str("f") {
+        Some(f) => f,
+        None => match output {
+            Some(ref o) => match Path::new(o.as_slice()).extension_str() {
+                Some(ext) => ext.to_string(),
+                None => "ascii".to_string()
+            },
+            None => "ascii".to_string()
+        }
+    };
+    // check before the output is created, which truncates it
+    let pixels = match format.as_slice() {
+        "ppm" | "png" | "svg" => true,
+        "ascii" | "csv" => false,
+        f => return fail(format!("unknown format {}", f))
+    };
+    let ascii = format.as_slice() == "ascii";
+    let (width, height) = match m.opt_str("s") {
+        Some(s) => match parse_size(s.as_slice(), pixels) {
+            Some(size) => size,
+            None => return fail(format!("bad size {}, expected e.g. 200x150", s))
+        },
+        None => if ascii {(60, 20)} else {(200, 150)}
+    };
+    let modes = match m.opt_str("m") {
+        Some(s) => match parse_mode(s.as_slice()) {
+            Some(modes) => Some(modes),
+            None => return fail(format!("unknown mode {}", s))
+        },
+        None => None
+    };
+    let samples = match m.opt_str("n").map(|n| from_str::<uint>(n.as_slice())) {
+        Some(Some(n)) if n > 0 => n,
+        Some(_) => return fail("the number of samples must be positive".to_string()),
+        None => if ascii {width * 2} else {width}
+    };
+    let columns = match m.opt_str("c").map(|n| from_str::<uint>(n.as_slice())) {
+        Some(Some(n)) if n > 0 => n,
+        Some(_) => return fail("the number of columns must be positive".to_string()),
+        None => match modes {
+            Some(ref modes) if modes.len() > 1 => modes.len(),
+            _ => 4
+        }
+    };
+
+    // a mode given by the name, e.g. easeOutQuint, is used unless -m is
+    let registry = Registry::with_builtins();
+    let mut cells = Vec::new();
+    for name in m.free.iter() {
+        let (ease, named) = match registry.lookup(name.as_slice()) {
+            Some(found) => found,
+            None => return fail(format!("unknown easing {}", name))
+        };
+        let modes = match modes {
+            Some(ref modes) => modes.clone(),
+            None => vec![named]
+        };
+        for mode in modes.into_iter() {
+            cells.push(Cell::new(format!("{} {}", ease.name(), mode), &ease, mode, samples));
+        }
+    }
+    let columns = if columns > cells.len() {cells.len()} else {columns};
+    let grid = Grid {cells: cells, columns: columns, width: width, height: height};
+
+    let mut out: Box<Writer> = match output {
+        Some(ref o) => match File::create(&Path::new(o.as_slice())) {
+            Ok(f) => box f as Box<Writer>,
+            Err(e) => return fail(format!("can't write {}: {}", o, e))
+        },
+        None => box io::stdout() as Box<Writer>
+    };
+    let res = match format.as_slice() {
+        "ppm" => write_ppm(&rasterize(&grid), &mut *out),
+        "png" => write_png(&rasterize(&grid), &mut *out),
+        "svg" => write_svg(&grid, &mut *out),
+        "ascii" => write_ascii(&grid, &mut *out),
+        "csv" => write_csv(&grid, &mut *out),
+        _ => unreachable!()
+    };
+    match res.and_then(|_| out.flush()) {
+        Ok(()) => {}
+        Err(e) => fail(format!("writing failed: {}", e))
+    }
+}
@@ -0,0 +1,275 @@
+//! Tweening a field of a larger value, without raw pointers.
+//!
+//! A `Lens` is a pair of functions reading and writing one field of a
+//! struct. Paired with a way to reach the struct, it is an `Access` to that
+//! field. Lenses are made with the `lens!` macro, or generated for several
+//! fields of a struct at once with `lenses!`:
+//!
+//! ```rust
+//! #![feature(phase)]
+//! #[phase(plugin, link)] extern crate tween;
+//!
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//! use tween::{Tween, to};
+//! use tween::ease::{quint, InOut};
+//!
+//! struct Pos { x: f64, y: f64 }
+//! struct Sprite { alpha: f32 }
+//! struct Player { pos: Pos, sprite: Sprite }
+//!
+//! lenses! {
+//!     Player {
+//!         x: pos.x -> f64,
+//!         alpha: sprite.alpha -> f32
+//!     }
+//! }
+//!
+//! fn main() {
+//!     let player = Rc::new(RefCell::new(Player {
+//!         pos: Pos {x: 0., y: 0.},
+//!         sprite: Sprite {alpha: 1.}
+//!     }));
+//!     let mut tw = to(Player::x().on_rc(player.clone()), 100., quint(), InOut, 2.);
+//!     tw.update(1.);
+//! }
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use Access;
+
+/// Reads and writes a `T` inside an `S`.
+pub struct Lens<S, T> {
+    pub get: fn(&S) -> T,
+    pub set: fn(&mut S, T)
+}
+
+impl<S, T> Clone for Lens<S, T> {
+    fn clone(&self) -> Lens<S, T> {
+        Lens {get: self.get, set: self.set}
+    }
+}
+
+impl<S, T: Copy> Lens<S, T> {
+    /// Access the field of a struct behind an `Rc<RefCell<_>>`. The cell is
+    /// borrowed for every read and write only, so it must not be borrowed
+    /// mutably elsewhere while the tween is updated.
+    pub fn on_rc(&self, target: Rc<RefCell<S>>) -> RcLens<S, T> {
+        RcLens {target: target, lens: self.clone()}
+    }
+
+    /// Access the field of a struct in a `Cell`. Every write copies the
+    /// struct out of the cell and back.
+    pub fn on_cell<'a>(&self, target: &'a Cell<S>) -> CellLens<'a, S, T> {
+        CellLens {target: target, lens: self.clone()}
+    }
+
+    /// Access the field of a struct stored in an arena. The key must be
+    /// valid, as the field is read right away.
+    pub fn on_slot(&self, arena: &Arena<S>, key: Key) -> SlotLens<S, T> {
+        let get = self.get;
+        let val = match arena.with(key.clone(), |s| get(&*s)) {
+            Some(v) => v,
+            None => panic!("no value for {} in the arena", key)
+        };
+        SlotLens {arena: arena.clone(), key: key, lens: self.clone(), last: Rc::new(Cell::new(val))}
+    }
+}
+
+/// A lens for a field path of a struct, e.g. `lens!(Player, pos.x -> f64)`.
+#[macro_export]
+macro_rules! lens(
+    ($s:ty, $first:ident $(. $rest:ident)* -> $t:ty) => ({
+        fn get(s: &$s) -> $t {
+            s.$first $(.$rest)*
+        }
+        fn set(s: &mut $s, v: $t) {
+            s.$first $(.$rest)* = v;
+        }
+        ::tween::lens::Lens {get: get, set: set}
+    })
+)
+
+/// Generate a function returning a lens for each listed field path, in an
+/// `impl` of the struct.
+#[macro_export]
+macro_rules! lenses(
+    ($s:ident { $($name:ident : $first:ident $(. $rest:ident)* -> $t:ty),+ }) => (
+        impl $s {
+            $(
+                #[allow(dead_code)]
+                pub fn $name() -> ::tween::lens::Lens<$s, $t> {
+                    lens!($s, $first $(. $rest)* -> $t)
+                }
+            )+
+        }
+    )
+)
+
+/// A field of a struct behind an `Rc<RefCell<_>>`.
+pub struct RcLens<S, T> {
+    target: Rc<RefCell<S>>,
+    lens: Lens<S, T>
+}
+
+impl<S, T> Clone for RcLens<S, T> {
+    fn clone(&self) -> RcLens<S, T> {
+        RcLens {target: self.target.clone(), lens: self.lens.clone()}
+    }
+}
+
+/// Panics like the `Rc<RefCell<T>>` accessor if the cell is borrowed
+/// elsewhere.
+impl<S, T> Access<T> for RcLens<S, T> {
+    #[inline]
+    fn get(&self) -> T {
+        match self.target.try_borrow() {
+            Some(s) => (self.lens.get)(&*s),
+            None => panic!("tweened RefCell is already mutably borrowed")
+        }
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        match self.target.try_borrow_mut() {
+            Some(mut s) => (self.lens.set)(&mut *s, val),
+            None => panic!("tweened RefCell is already borrowed")
+        }
+    }
+}
+
+/// A field of a struct in a `Cell`.
+pub struct CellLens<'a, S: 'a, T> {
+    target: &'a Cell<S>,
+    lens: Lens<S, T>
+}
+
+impl<'a, S, T> Clone for CellLens<'a, S, T> {
+    fn clone(&self) -> CellLens<'a, S, T> {
+        CellLens {target: self.target, lens: self.lens.clone()}
+    }
+}
+
+impl<'a, S: Copy, T> Access<T> for CellLens<'a, S, T> {
+    #[inline]
+    fn get(&self) -> T {
+        (self.lens.get)(&self.target.get())
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        let mut s = self.target.get();
+        (self.lens.set)(&mut s, val);
+        self.target.set(s);
+    }
+}
+
+/// Identifies a value in an `Arena`. Keys of removed values stay invalid,
+/// even when their slot is reused.
+#[deriving(Clone, PartialEq, Eq, Show)]
+pub struct Key {
+    index: uint,
+    generation: uint
+}
+
+struct Entry<S> {
+    generation: uint,
+    val: Option<S>
+}
+
+/// Shared storage for values that tweens write to, e.g. the game objects
+/// of a level. Cloning the arena shares the storage.
+pub struct Arena<S> {
+    entries: Rc<RefCell<Vec<Entry<S>>>>
+}
+
+impl<S> Clone for Arena<S> {
+    fn clone(&self) -> Arena<S> {
+        Arena {entries: self.entries.clone()}
+    }
+}
+
+impl<S> Arena<S> {
+    pub fn new() -> Arena<S> {
+        Arena {entries: Rc::new(RefCell::new(Vec::new()))}
+    }
+
+    /// Store a value, reusing a free slot if there is one.
+    pub fn insert(&self, val: S) -> Key {
+        let mut entries = self.entries.borrow_mut();
+        match entries.iter().position(|e| e.val.is_none()) {
+            Some(i) => {
+                let e = &mut entries[i];
+                e.generation += 1;
+                e.val = Some(val);
+                Key {index: i, generation: e.generation}
+            }
+            None => {
+                entries.push(Entry {generation: 0, val: Some(val)});
+                Key {index: entries.len() - 1, generation: 0}
+            }
+        }
+    }
+
+    /// Take a value out. Tweens still writing to it do nothing from now on.
+    pub fn remove(&self, key: Key) -> Option<S> {
+        let mut entries = self.entries.borrow_mut();
+        match entries.get_mut(key.index) {
+            Some(e) if e.generation == key.generation => e.val.take(),
+            _ => None
+        }
+    }
+
+    /// Call `f` with the value, if it's still there.
+    pub fn with<R>(&self, key: Key, f: |&mut S| -> R) -> Option<R> {
+        let mut entries = self.entries.borrow_mut();
+        match entries.get_mut(key.index) {
+            Some(e) if e.generation == key.generation => e.val.as_mut().map(f),
+            _ => None
+        }
+    }
+}
+
+/// A field of a struct in an `Arena`. Once the struct is removed, writes
+/// are dropped and reads return the last value read or written, or the
+/// value when the lens was made. Clones share that value.
+pub struct SlotLens<S, T> {
+    arena: Arena<S>,
+    key: Key,
+    lens: Lens<S, T>,
+    last: Rc<Cell<T>>
+}
+
+impl<S, T: Copy> Clone for SlotLens<S, T> {
+    fn clone(&self) -> SlotLens<S, T> {
+        SlotLens {
+            arena: self.arena.clone(),
+            key: self.key.clone(),
+            lens: self.lens.clone(),
+            last: self.last.clone()
+        }
+    }
+}
+
+impl<S, T: Copy> Access<T> for SlotLens<S, T> {
+    #[inline]
+    fn get(&self) -> T {
+        let get = self.lens.get;
+        match self.arena.with(self.key.clone(), |s| get(&*s)) {
+            Some(v) => {
+                self.last.set(v);
+                v
+            }
+            None => self.last.get()
+        }
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        let set = self.lens.set;
+        self.last.set(val);
+        self.arena.with(self.key.clone(), |s| set(s, val));
+    }
+}
@@ -1,24 +1,60 @@
 #![crate_name = "tween"]
 #![crate_type = "lib"]
+#![feature(macro_rules)]
+
+extern crate serialize;
+extern crate time;
 
 use std::cmp;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use std::f64::INFINITY;
-use std::num::{ToPrimitive, FromPrimitive};
+use std::num::{Zero, ToPrimitive, FromPrimitive};
+use std::time::Duration;
 
 use partial_iter::PartialExtremes;
+use snapshot::{Snapshot, Reader};
+use describe::Node;
 
 use ease::Ease;
 
 pub mod partial_iter;
+#[macro_escape]
+pub mod lens;
+#[macro_escape]
+pub mod props;
+#[macro_escape]
+pub mod dsl;
+pub mod math;
 pub mod ease;
+pub mod registry;
+pub mod lut;
+pub mod lottie;
+pub mod sync;
+pub mod runner;
+pub mod manager;
+pub mod clock;
+pub mod blend;
+pub mod noise;
+pub mod decay;
+pub mod batch;
+pub mod describe;
+pub mod plot;
+pub mod snapshot;
+pub mod builder;
+#[cfg(feature = "async")]
+pub mod async;
+#[cfg(feature = "threads")]
+pub mod pool;
 
 /// Any data that can be interpolated by this library.
 pub trait Tweenable: Add<Self, Self> + Sub<Self, Self> + MulWithF64 + Float + FloatMath + Copy {}
 
 /// A mutable property which is passed to the tweens.
 /// Chosen because hardcoding access ways is inflexible.
-pub trait Access<T>: Copy {
+/// Accessors are cloned along with the tweens holding them, so cloning one
+/// must still refer to the same property.
+pub trait Access<T>: Clone {
     #[inline]
     fn get(&self) -> T;
     #[inline]
@@ -27,7 +63,7 @@ pub trait Access<T>: Copy {
 
 /// A single part of a tween tree.
 /// Can do almost anything, examples currently implemented are
-/// `Single`, `Multi`, `Sequence`, `Parallel`, `Pause` and `Exec`.
+/// `Single`, `Multi`, `Relative`, `Sequence`, `Parallel`, `Pause` and `Exec`.
 pub trait Tween: Sized + Clone {
     /// The amount of time remaining in this tween. Passing this value to
     /// `update` should make `done` return true
@@ -44,10 +80,54 @@ pub trait Tween: Sized + Clone {
     #[inline]
     fn reset(&mut self);
 
-    /// Update the tween, after `delta` time has passed
+    /// Update the tween, after `delta` time has passed.
+    /// Returns `delta` minus the time that was remaining before the update.
+    /// If that isn't negative, the tween finished and it is the time left
+    /// over, which a `Sequence` passes on to its next tween and a `Repeat`
+    /// to its next cycle. If it is negative, the tween is still running,
+    /// and it is minus the time still needed.
+    /// Combinators return it for all their children: a `Parallel` for its
+    /// longest child, and a `Repeat` without a limit for all its cycles,
+    /// so always negative infinity. An `Exec` takes no time and returns all
+    /// of `delta`.
     #[inline]
     fn update(&mut self, delta: f64) -> f64;
 
+    /// Update the tween by a `Duration`, counted in seconds.
+    #[inline]
+    fn advance(&mut self, delta: Duration) -> f64 {
+        self.update(clock::secs(delta))
+    }
+
+    /// Append the timing state of this tween and its children to `snap`.
+    /// Tweens that don't override this save nothing, so `restore` leaves
+    /// them where they are.
+    fn save(&self, _snap: &mut Snapshot) {}
+
+    /// Read back the state written by `save`, in the same order.
+    fn load(&mut self, _snap: &mut Reader) {}
+
+    /// Take a snapshot of the state of this tween, see `snapshot`.
+    fn snapshot(&self) -> Snapshot {
+        let mut snap = Snapshot::new();
+        self.save(&mut snap);
+        snap
+    }
+
+    /// Go back to the state of a snapshot taken of this tween, or a clone
+    /// of it.
+    fn restore(&mut self, snap: &Snapshot) {
+        let mut r = snap.reader();
+        self.load(&mut r);
+        assert!(r.is_empty(), "snapshot doesn't match the tween tree");
+    }
+
+    /// Describe the state of this tween and its children, see `describe`.
+    /// Tweens that don't override this report an elapsed time of 0.
+    fn describe(&self) -> Node {
+        Node::new("Tween", 0., self.remaining())
+    }
+
     /// Yeah, this hurts. I know. But apparently, just because this trait
     /// is `Clone` doesn't mean that `Box<Tween>` is `Clone`...
     fn clone_into_box<'a>(&self) -> Box<Tween + 'a> {
@@ -58,7 +138,47 @@ pub trait Tween: Sized + Clone {
 impl<'a> Clone for Box<Tween + 'a> {
     #[inline]
     fn clone(&self) -> Box<Tween + 'a> {
-        self.clone_into_box()
+        (**self).clone_into_box()
+    }
+}
+
+/// A boxed tween is a tween as well, so combinators can hold either boxes
+/// or, with the `threads` feature, `Box<SendTween + Send>`.
+impl<'b> Tween for Box<Tween + 'b> {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        (**self).remaining()
+    }
+
+    #[inline]
+    fn done(&self) -> bool {
+        (**self).done()
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    #[inline]
+    fn update(&mut self, delta: f64) -> f64 {
+        (**self).update(delta)
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        (**self).save(snap)
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        (**self).load(snap)
+    }
+
+    fn describe(&self) -> Node {
+        (**self).describe()
+    }
+
+    fn clone_into_box<'a>(&self) -> Box<Tween + 'a> {
+        (**self).clone_into_box()
     }
 }
 
@@ -112,6 +232,45 @@ impl<'a, T: Copy + 'a> Access<T> for &'a Cell<T> {
     }
 }
 
+/// Allow access/tweening via a shared Cell<T>. Unlike `&Cell<T>` this
+/// doesn't borrow, so the tween can be `'static` and be used in `seq`,
+/// `par` and a `Manager`.
+impl<T: Copy> Access<T> for Rc<Cell<T>> {
+    #[inline]
+    fn get(&self) -> T {
+        (**self).get()
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        (**self).set(val);
+    }
+}
+
+/// Allow access/tweening via a shared RefCell<T>, for values that other
+/// code borrows as well, e.g. to change them in place. The value is copied
+/// in and out, so it still has to be `Copy`.
+/// The cell is only borrowed for each single read or write. Updating the
+/// tween while the cell is mutably borrowed elsewhere, or writing while it
+/// is borrowed at all, panics, just like borrowing it twice would.
+impl<T: Copy> Access<T> for Rc<RefCell<T>> {
+    #[inline]
+    fn get(&self) -> T {
+        match self.try_borrow() {
+            Some(v) => *v,
+            None => panic!("tweened RefCell is already mutably borrowed")
+        }
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        match self.try_borrow_mut() {
+            Some(mut v) => *v = val,
+            None => panic!("tweened RefCell is already borrowed")
+        }
+    }
+}
+
 /// Access to anything that can't be done via the other two access modes
 /// via callback functions to do what you want.
 /// Also sensible if you want to avoid polling the value, but get direct
@@ -130,8 +289,8 @@ impl<'a, T: 'a, G: Fn<(), T>, S: Fn<(T), ()>> Access<T> for (&'a G, &'a S) {
 
 /// Unsafe access/tweening via mutable raw pointers.
 /// Added to minimize changes to your preexisting model.
-/// If you can, please use the `Cell<T>` alternative, as
-/// this thing shouldn't exist.
+/// If you can, please use the `Cell<T>` alternative or a lens from the
+/// `lens` module, as this thing shouldn't exist.
 impl<T: Copy> Access<T> for *mut T {
     #[inline]
     fn get(&self) -> T {
@@ -178,6 +337,15 @@ impl<T: Tweenable, A: Access<T>, E: Ease> Single<T, A, E> {
         }
     }
 
+    /// How fast the value is changing, in value units per time unit. Zero
+    /// for a tween that takes no time.
+    pub fn velocity(&self) -> T {
+        if self.duration <= 0. {
+            return Zero::zero();
+        }
+        let v = self.ease.velocity(self.mode, self.current / self.duration) / self.duration;
+        (self.end - self.start).mul_with_f64(v)
+    }
 }
 
 impl<T: Tweenable + 'static, A: Access<T> + Clone, E: Ease + Clone> Tween for Single<T, A, E> {
@@ -193,14 +361,107 @@ impl<T: Tweenable + 'static, A: Access<T> + Clone, E: Ease + Clone> Tween for Si
 
     #[inline]
     fn update(&mut self, delta: f64) -> f64 {
-        let t = self.current / self.duration;
+        let remain = self.remaining();
+        self.current += cmp::partial_min(remain, delta).unwrap();
+        let t = if self.duration > 0. {self.current / self.duration} else {1.};
         let a = self.ease.ease(self.mode, t);
         let old = self.acc.get();
         let new = old.lerp(&self.start, &self.end, a);
         self.acc.set(new);
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("Single", self.current, self.remaining())
+            .ease(format!("{} {}", self.ease.name(), self.mode))
+    }
+}
+
+/// A tween that adds to a value instead of overwriting it. On each update
+/// only the change since its last update is added, so several of these can
+/// run on the same property, and on top of absolute tweens, and their
+/// effects stack in the order they're updated.
+#[deriving(Clone)]
+pub struct Relative<T, A: Access<T>, E: Ease> {
+    acc: A,
+    start: T,
+    end: T,
+    applied: T,
+    relative: bool,
+    current: f64,
+    duration: f64,
+    ease: E,
+    mode: ease::Mode
+}
+
+impl<T: Tweenable, A: Access<T>, E: Ease> Relative<T, A, E> {
+    fn new(acc: A, start: T, end: T, relative: bool, ease: E, mode: ease::Mode, duration: f64)
+    -> Relative<T, A, E> {
+        Relative {
+            acc: acc,
+            start: start,
+            end: end,
+            applied: start.mul_with_f64(0.),
+            relative: relative,
+            current: 0f64,
+            duration: duration,
+            ease: ease,
+            mode: mode
+        }
+    }
+}
+
+impl<T: Tweenable + 'static, A: Access<T> + Clone, E: Ease + Clone> Tween for Relative<T, A, E> {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        self.duration - self.current
+    }
+
+    /// A relative tween keeps the change it made when reset, so repeating
+    /// it keeps adding. An additive one keeps its offset in the value and
+    /// replaces it from the next update on.
+    #[inline]
+    fn reset(&mut self) {
+        self.current = 0f64;
+        if self.relative {
+            self.applied = self.applied.mul_with_f64(0.);
+        }
+    }
+
+    #[inline]
+    fn update(&mut self, delta: f64) -> f64 {
         let remain = self.remaining();
         self.current += cmp::partial_min(remain, delta).unwrap();
-        -remain
+        let t = if self.duration > 0. {self.current / self.duration} else {1.};
+        let a = self.ease.ease(self.mode, t);
+        let offset = self.start.lerp(&self.start, &self.end, a);
+        let val = self.acc.get();
+        self.acc.set(val + (offset - self.applied));
+        self.applied = offset;
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+        snap.push_value(self.applied);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+        self.applied = snap.value();
+    }
+
+    fn describe(&self) -> Node {
+        Node::new(if self.relative {"Relative"} else {"Additive"}, self.current, self.remaining())
+            .ease(format!("{} {}", self.ease.name(), self.mode))
     }
 }
 
@@ -226,6 +487,19 @@ impl<T: Tweenable, A: Access<T>, E: Ease> Multi<T, A, E> {
             current_time: 0.
         }
     }
+
+    /// How fast the value is changing in the current data point, in value
+    /// units per time unit. Zero once all data points are done, or without
+    /// any.
+    pub fn velocity(&self) -> T {
+        match self.data.get(self.current) {
+            Some(&(start, end, dur, mode)) if self.current_time < dur || self.current + 1 < self.data.len() => {
+                let v = self.ease.velocity(mode, self.current_time / dur) / dur;
+                (end - start).mul_with_f64(v)
+            }
+            _ => Zero::zero()
+        }
+    }
 }
 
 impl <T: Tweenable, A: Access<T> + Clone, E: Ease> Tween for Multi<T, A, E> {
@@ -244,7 +518,8 @@ impl <T: Tweenable, A: Access<T> + Clone, E: Ease> Tween for Multi<T, A, E> {
     fn update(&mut self, delta: f64) -> f64 {
         //let (_, _, dur, _) = self.data[self.current];
         //delta /= dur; // normalize from duration to [0;1]
-        self.current_time += delta;
+        let remain = self.remaining();
+        self.current_time += cmp::partial_min(remain, delta).unwrap();
 
         // wrap time around till between bounds
         loop {
@@ -261,22 +536,38 @@ impl <T: Tweenable, A: Access<T> + Clone, E: Ease> Tween for Multi<T, A, E> {
         let a = self.ease.ease(mode, self.current_time / dur);
         let new = self.acc.get().lerp(&start, &end, a);
         self.acc.set(new);
-        delta
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_uint(self.current);
+        snap.push_f64(self.current_time);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.uint();
+        self.current_time = snap.f64();
     }
 
+    fn describe(&self) -> Node {
+        let done = self.data.iter().take(self.current).fold(0., |a, &(_, _, d, _)| a + d);
+        Node::new("Multi", done + self.current_time, self.remaining())
+            .ease(self.ease.name())
+            .current(self.current)
+    }
 }
 
 /// A tween that runs other tweens to completion, in order.
 /// It will switch to the next tween in the vector once the current tween
 /// returns `true` when `done` is called.
 #[deriving(Clone)]
-pub struct Sequence<'a> {
-    tweens: Vec<Box<Tween + 'a>>,
+pub struct Sequence<T> {
+    tweens: Vec<T>,
     current: uint
 }
 
-impl<'a> Sequence<'a> {
-    fn new(tweens: Vec<Box<Tween + 'a>>) -> Sequence<'a> {
+impl<T: Tween> Sequence<T> {
+    pub fn new(tweens: Vec<T>) -> Sequence<T> {
         Sequence {
             tweens: tweens,
             current: 0u
@@ -284,7 +575,7 @@ impl<'a> Sequence<'a> {
     }
 }
 
-impl<'a> Tween for Sequence<'a> {
+impl<T: Tween> Tween for Sequence<T> {
     #[inline]
     fn remaining(&self) -> f64 {
         self.tweens.iter().fold(0f64, |a, b| a + b.remaining())
@@ -300,14 +591,42 @@ impl<'a> Tween for Sequence<'a> {
 
     #[inline]
     fn update(&mut self, delta: f64) -> f64 {
+        let before = self.remaining();
         let mut remain: f64 = delta;
-        while remain > 0f64 && self.current < self.tweens.len() {
+        while self.current < self.tweens.len() {
+            // parts taking no time right after the end of the last one,
+            // like an `Exec`, are run with it rather than on the next update
+            if remain < 0f64 || remain == 0f64 && self.tweens[self.current].remaining() > 0f64 {
+                break;
+            }
             remain = self.tweens.get_mut(self.current).update(remain);
             if self.tweens[self.current].done() {
                 self.current += 1;
             }
         }
-        remain
+        delta - before
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_uint(self.current);
+        for tw in self.tweens.iter() {
+            tw.save(snap);
+        }
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.uint();
+        for tw in self.tweens.iter_mut() {
+            tw.load(snap);
+        }
+    }
+
+    fn describe(&self) -> Node {
+        let children: Vec<Node> = self.tweens.iter().map(|tw| tw.describe()).collect();
+        let elapsed = children.iter().fold(0., |a, c| a + c.elapsed);
+        Node::new("Sequence", elapsed, self.remaining())
+            .current(self.current)
+            .children(children)
     }
 }
 
@@ -317,41 +636,76 @@ impl<'a> Tween for Sequence<'a> {
 /// finished.
 #[deriving(Clone)]
 pub struct Parallel {
-    tweens: Vec<Box<Tween + 'static>>
+    tweens: Vec<Box<Tween + 'static>>,
+    /// Whether it was updated since the last reset.
+    started: bool
 }
 
 impl Parallel {
     fn new(tweens: Vec<Box<Tween + 'static>>) -> Parallel {
         Parallel {
-            tweens: tweens
+            tweens: tweens,
+            started: false
         }
     }
 }
 
 impl Tween for Parallel {
-    /// The max remaining time of all wrapped tweens
+    /// The max remaining time of all wrapped tweens, 0 without any.
     #[inline]
     fn remaining(&self) -> f64 {
-        self.tweens.iter().partial_max_by(|&a| a.remaining()).unwrap().remaining()
+        match self.tweens.iter().partial_max_by(|&a| a.remaining()) {
+            Some(tw) => tw.remaining(),
+            None => 0.
+        }
     }
 
     /// Reset every wrapped tween.
     #[inline]
     fn reset(&mut self) {
+        self.started = false;
         for tw in self.tweens.iter_mut() {
             tw.reset();
         }
     }
 
+    /// Children that are done, like an `Exec` that was called, are left
+    /// alone unless going backwards. The first update after a reset still
+    /// reaches all of them, so ones without duration write their values.
     #[inline]
     fn update(&mut self, delta: f64) -> f64 {
         let mut max_remain = 0f64;
         for tw in self.tweens.iter_mut() {
             let remain = tw.remaining();
             if remain > max_remain { max_remain = remain; }
+            if self.started && delta >= 0. && tw.done() {
+                continue;
+            }
             tw.update(cmp::partial_min(remain, delta).unwrap());
         }
-        max_remain - delta
+        self.started = true;
+        delta - max_remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_bool(self.started);
+        for tw in self.tweens.iter() {
+            tw.save(snap);
+        }
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.started = snap.bool();
+        for tw in self.tweens.iter_mut() {
+            tw.load(snap);
+        }
+    }
+
+    fn describe(&self) -> Node {
+        let children: Vec<Node> = self.tweens.iter().map(|tw| tw.describe()).collect();
+        let elapsed = children.iter().fold(0., |a, c| if c.elapsed > a {c.elapsed} else {a});
+        Node::new("Parallel", elapsed, self.remaining())
+            .children(children)
     }
 }
 
@@ -384,7 +738,19 @@ impl Tween for Pause {
     fn update(&mut self, delta: f64) -> f64 {
         let remain = self.remaining();
         self.current += cmp::partial_min(remain, delta).unwrap();
-        -remain
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("Pause", self.current, self.remaining())
     }
 }
 
@@ -397,7 +763,7 @@ pub struct Exec {
 }
 
 impl Exec {
-    fn new(content: fn()) -> Exec {
+    pub fn new(content: fn()) -> Exec {
         Exec {content: content, executed: false}
     }
 }
@@ -411,50 +777,139 @@ impl Tween for Exec {
     fn reset(&mut self) {self.executed = false;}
     #[inline]
     fn update(&mut self, delta: f64) -> f64 {
-        (self.content)();
+        call(self.content);
         self.executed = true;
         delta // Exec consumes no time
     }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_bool(self.executed);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.executed = snap.bool();
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("Exec", 0., 0.)
+    }
+}
+
+/// Call an `Exec` function, queued by the pool if a worker is updating.
+#[cfg(feature = "threads")]
+#[inline]
+fn call(f: fn()) {
+    pool::run(f)
 }
 
-/// Repeat a given tween forever.
+#[cfg(not(feature = "threads"))]
+#[inline(always)]
+fn call(f: fn()) {
+    f()
+}
+
+/// Repeat a given tween forever, or a given number of times.
 #[deriving(Clone)]
-pub struct Repeat {
-    tween: Box<Tween + 'static>
+pub struct Repeat<T> {
+    tween: T,
+    count: uint,
+    /// The number of plays, `None` for forever.
+    limit: Option<uint>,
+    /// The duration of one play.
+    cycle: f64
 }
 
-impl Repeat {
-    pub fn new(tween: Box<Tween + 'static>) -> Repeat {
+impl<T: Tween> Repeat<T> {
+    pub fn new(tween: T) -> Repeat<T> {
+        let mut fresh = tween.clone();
+        fresh.reset();
         Repeat {
-            tween: tween
+            tween: tween,
+            count: 0,
+            limit: None,
+            cycle: fresh.remaining()
+        }
+    }
+
+    /// Play `tween` `n` times, then stop at its end.
+    pub fn times(tween: T, n: uint) -> Repeat<T> {
+        let mut fresh = tween.clone();
+        fresh.reset();
+        Repeat {
+            tween: tween,
+            count: 0,
+            limit: Some(n),
+            cycle: fresh.remaining()
+        }
+    }
+
+    fn finished(&self) -> bool {
+        match self.limit {
+            Some(n) => self.count >= n,
+            None => false
         }
     }
 }
 
-impl Tween for Repeat {
-    #[inline(always)]
+impl<T: Tween> Tween for Repeat<T> {
+    #[inline]
     fn remaining(&self) -> f64 {
-        INFINITY
+        match self.limit {
+            None => INFINITY,
+            Some(n) if self.count >= n => 0.,
+            Some(n) => self.tween.remaining() + (n - self.count - 1) as f64 * self.cycle
+        }
     }
 
     #[inline]
     fn reset(&mut self) {
         self.tween.reset();
+        self.count = 0;
     }
 
     #[inline]
     fn update(&mut self, delta: f64) -> f64 {
+        let before = self.remaining();
         let mut remain = delta;
-        while remain > 0. {
+        while remain > 0. && !self.finished() {
             let rest = self.tween.update(remain);
-            if rest >= 0. {
-                self.tween.reset();
-            } else {
-                // negative rest means: current cycle still running
-                remain += rest;
+            if rest < 0. {
+                // the current cycle is still running
+                break;
+            }
+            self.count += 1;
+            if self.finished() {
+                // stay at the end of the last play
+                break;
             }
+            self.tween.reset();
+            // a cycle that takes no time would never use up the rest
+            if rest >= remain && self.limit.is_none() {
+                break;
+            }
+            remain = rest;
         }
-        0. // It can always continue, so there is no rest
+        delta - before // without a limit, it never finishes
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_uint(self.count);
+        self.tween.save(snap);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.count = snap.uint();
+        self.tween.load(snap);
+    }
+
+    /// The elapsed time counts all completed cycles, the child's only the
+    /// current one.
+    fn describe(&self) -> Node {
+        let child = self.tween.describe();
+        let played = if self.count > 0 {self.count as f64 * self.cycle} else {0.};
+        Node::new("Repeat", played + child.elapsed, self.remaining())
+            .repeats(self.count)
+            .children(vec![child])
     }
 }
 
@@ -465,17 +920,21 @@ impl Tween for Repeat {
 pub struct Reverse {
     tween: Box<Tween + 'static>,
     current: f64,
-    duration: f64
+    duration: f64,
+    /// The state of the wrapped tween at its end, to rewind to on reset.
+    end: Snapshot
 }
 
 impl Reverse {
     pub fn new(mut tween: Box<Tween + 'static>) -> Reverse {
         let rem = tween.remaining();
         tween.update(rem);
+        let end = tween.snapshot();
         Reverse {
             tween: tween,
             current: 0.,
-            duration: rem
+            duration: rem,
+            end: end
         }
     }
 }
@@ -486,14 +945,37 @@ impl Tween for Reverse {
         self.duration - self.current
     }
 
+    /// Moves the wrapped tween back to its end, by restoring its state from
+    /// then instead of playing it again, so no `Exec` fires and nothing is
+    /// written. Tweens that don't `save` their state aren't moved.
     #[inline]
     fn reset(&mut self) {
         self.current = 0.;
+        self.tween.restore(&self.end);
     }
 
     #[inline]
     fn update(&mut self, delta: f64) -> f64 {
-        self.tween.update(-delta)
+        let remain = self.remaining();
+        let step = cmp::partial_min(remain, delta).unwrap();
+        self.current += step;
+        self.tween.update(-step);
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+        self.tween.save(snap);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+        self.tween.load(snap);
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("Reverse", self.current, self.remaining())
+            .children(vec![self.tween.describe()])
     }
 }
 
@@ -509,14 +991,32 @@ pub fn from_to<T: Tweenable + 'static, A: Access<T>, E: Ease>
 pub fn to<T: Tweenable + Clone + 'static, A: Access<T>, E: Ease>
 (val: A, end: T, ease: E, mode: ease::Mode, duration: f64)
 -> Single<T, A, E> {
-    from_to(val, val.get(), end, ease, mode, duration)
+    let start = val.get();
+    from_to(val, start, end, ease, mode, duration)
 }
 
 /// Tween a value from a given value to its current value, given an easing, a mode and a duration.
 pub fn from<T: Tweenable + Clone + 'static, A: Access<T>, E: Ease>
 (val: A, start: T, ease: E, mode: ease::Mode, duration: f64)
 -> Single<T, A, E> {
-    from_to(val, start, val.get(), ease, mode, duration)
+    let end = val.get();
+    from_to(val, start, end, ease, mode, duration)
+}
+
+/// Tween a value by a given amount, relative to whatever it is while the
+/// tween runs, given an easing, a mode and a duration.
+pub fn by<T: Tweenable + 'static, A: Access<T>, E: Ease>
+(val: A, delta: T, ease: E, mode: ease::Mode, duration: f64)
+-> Relative<T, A, E> {
+    Relative::new(val, delta.mul_with_f64(0.), delta, true, ease, mode, duration)
+}
+
+/// Tween an offset between two bounds, that is added on top of the value
+/// instead of replacing it, given an easing, a mode and a duration.
+pub fn additive<T: Tweenable + 'static, A: Access<T>, E: Ease>
+(val: A, start: T, end: T, ease: E, mode: ease::Mode, duration: f64)
+-> Relative<T, A, E> {
+    Relative::new(val, start, end, false, ease, mode, duration)
 }
 
 /// Tween a value through several datapoints, each customized by start, end, easing and duration.
@@ -0,0 +1,591 @@
+//! Import of keyframe animation data exported from After Effects via
+//! Lottie (Bodymovin) JSON.
+//!
+//! Only the animation data is read, nothing is rendered. Every animated
+//! property of every layer is turned into one `Curve` per dimension, which
+//! can be played back with a `Track` writing through any `Access<f64>`.
+
+use std::fmt;
+use serialize::json;
+use serialize::json::Json;
+
+use {Tween, Access, par, delay};
+use ease::{Ease, In, BezierEase, bezier};
+use snapshot::{Snapshot, Reader};
+use describe::Node;
+
+/// Things that can go wrong while reading a Lottie file.
+#[deriving(Clone)]
+pub enum LottieError {
+    /// The input is not valid JSON.
+    Syntax(json::ParserError),
+    /// A required key is missing.
+    Missing(&'static str),
+    /// A key has a value of the wrong type.
+    Invalid(&'static str)
+}
+
+impl fmt::Show for LottieError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Syntax(ref e) => write!(f, "invalid JSON: {}", e),
+            Missing(key) => write!(f, "missing key `{}`", key),
+            Invalid(key) => write!(f, "invalid value for key `{}`", key)
+        }
+    }
+}
+
+pub type LottieResult<T> = Result<T, LottieError>;
+
+/// One keyframe-to-keyframe interpolation of a single dimension.
+/// Times are in seconds, in the time of the layer, see `Layer::start_time`.
+#[deriving(Clone)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub from: f64,
+    /// First spatial control point, `from + to` for position properties,
+    /// otherwise on the straight line.
+    pub c1: f64,
+    /// Second spatial control point, `to + ti` for position properties.
+    pub c2: f64,
+    pub to: f64,
+    /// The temporal easing, `None` for hold keyframes.
+    pub ease: Option<BezierEase>
+}
+
+impl Segment {
+    #[inline]
+    fn value_at(&self, t: f64) -> f64 {
+        let ease = match self.ease {
+            Some(ref e) => e,
+            None => return self.from
+        };
+        let dur = self.end - self.start;
+        let a = if dur <= 0. {1.} else {ease.ease(In, (t - self.start) / dur)};
+        // cubic bezier through the spatial tangents, a straight line if
+        // there are none
+        let b = 1. - a;
+        b * b * b * self.from + 3. * b * b * a * self.c1 + 3. * b * a * a * self.c2 + a * a * a * self.to
+    }
+}
+
+/// The value of one dimension of a property over time.
+#[deriving(Clone)]
+pub struct Curve {
+    initial: f64,
+    segments: Vec<Segment>
+}
+
+impl Curve {
+    /// A curve that never changes.
+    pub fn constant(val: f64) -> Curve {
+        Curve {initial: val, segments: Vec::new()}
+    }
+
+    /// The value at time `t`, in seconds. Before the first keyframe the
+    /// first value is held, after the last keyframe the last one.
+    pub fn value_at(&self, t: f64) -> f64 {
+        let mut val = self.initial;
+        for seg in self.segments.iter() {
+            if t < seg.start {
+                break;
+            }
+            if t < seg.end {
+                return seg.value_at(t);
+            }
+            val = seg.to;
+        }
+        val
+    }
+
+    /// How fast the value changes at time `t`, per second.
+    pub fn velocity_at(&self, t: f64) -> f64 {
+        let h = 1e-4;
+        let lo = if t - h < 0. {0.} else {t - h};
+        (self.value_at(t + h) - self.value_at(lo)) / (t + h - lo)
+    }
+
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> f64 {
+        self.segments.last().map(|s| s.end).unwrap_or(0.)
+    }
+
+    /// The same curve, with every keyframe moved by `offset`.
+    pub fn shifted(&self, offset: f64) -> Curve {
+        Curve {
+            initial: self.initial,
+            segments: self.segments.iter().map(|s| Segment {
+                start: s.start + offset,
+                end: s.end + offset,
+                ..s.clone()
+            }).collect()
+        }
+    }
+
+    /// Returns true if the curve has any keyframes.
+    pub fn is_animated(&self) -> bool {
+        !self.segments.is_empty()
+    }
+}
+
+/// An animatable property of a layer, e.g. `position`, with one curve
+/// per dimension.
+#[deriving(Clone)]
+pub struct Property {
+    pub name: String,
+    pub dims: Vec<Curve>
+}
+
+/// A layer of the composition. Only its timing and transform are read.
+/// All times are in seconds.
+#[deriving(Clone)]
+pub struct Layer {
+    pub name: String,
+    /// The composition time the layer appears at.
+    pub in_point: f64,
+    /// The composition time the layer disappears at.
+    pub out_point: f64,
+    /// The composition time the time of the layer starts at, i.e. the
+    /// offset of its keyframes.
+    pub start_time: f64,
+    pub properties: Vec<Property>,
+    /// Maps layer time to source time, both in seconds.
+    pub time_remap: Option<Curve>
+}
+
+/// A whole Lottie composition.
+#[deriving(Clone)]
+pub struct Animation {
+    pub frame_rate: f64,
+    pub in_point: f64,
+    pub out_point: f64,
+    pub layers: Vec<Layer>
+}
+
+/// The transform keys of a layer, and the names they're bound as.
+static TRANSFORM: [(&'static str, &'static str), ..6] = [
+    ("a", "anchor"),
+    ("p", "position"),
+    ("s", "scale"),
+    ("r", "rotation"),
+    ("o", "opacity"),
+    ("sk", "skew")
+];
+
+fn number(j: &Json, key: &'static str) -> LottieResult<f64> {
+    j.as_f64().ok_or(Invalid(key))
+}
+
+fn field<'a>(j: &'a Json, key: &'static str) -> LottieResult<&'a Json> {
+    j.find(key).ok_or(Missing(key))
+}
+
+fn field_or(j: &Json, key: &'static str, default: f64) -> LottieResult<f64> {
+    match j.find(key) {
+        Some(v) => number(v, key),
+        None => Ok(default)
+    }
+}
+
+/// A flag, written as a boolean, or as 0 or 1 by older exporters.
+fn flag(j: &Json, key: &'static str) -> LottieResult<bool> {
+    match j.find(key) {
+        Some(v) => match v.as_boolean() {
+            Some(b) => Ok(b),
+            None => number(v, key).map(|n| n != 0.)
+        },
+        None => Ok(false)
+    }
+}
+
+/// A value that's either a number or a list of numbers, as a list.
+fn numbers(j: &Json, key: &'static str) -> LottieResult<Vec<f64>> {
+    match j.as_list() {
+        Some(l) => l.iter().map(|v| number(v, key)).collect(),
+        None => number(j, key).map(|v| vec![v])
+    }
+}
+
+/// Bezier handles are stored per dimension, but exporters may collapse
+/// them to a single number.
+fn handle(j: &Json, key: &'static str, dim: uint) -> LottieResult<(f64, f64)> {
+    let x = try!(numbers(try!(field(j, "x")), key));
+    let y = try!(numbers(try!(field(j, "y")), key));
+    if x.is_empty() || y.is_empty() {
+        return Err(Invalid(key));
+    }
+    Ok((x[cmp_min(dim, x.len() - 1)], y[cmp_min(dim, y.len() - 1)]))
+}
+
+#[inline]
+fn cmp_min(a: uint, b: uint) -> uint {
+    if a < b {a} else {b}
+}
+
+impl Property {
+    /// Read a property object, `{"a": false|true, "k": ...}`. Keyframe
+    /// times are converted from frames to seconds with `fps`.
+    pub fn from_json(name: &str, j: &Json, fps: f64) -> LottieResult<Property> {
+        let animated = try!(flag(j, "a"));
+        let k = try!(field(j, "k"));
+
+        if !animated {
+            let vals = try!(numbers(k, "k"));
+            return Ok(Property {
+                name: name.to_string(),
+                dims: vals.iter().map(|&v| Curve::constant(v)).collect()
+            });
+        }
+
+        let frames = try!(k.as_list().ok_or(Invalid("k")));
+        let mut dims: Vec<Curve> = Vec::new();
+
+        for (n, kf) in frames.iter().enumerate() {
+            let start = try!(number(try!(field(kf, "t")), "t")) / fps;
+            let from = match kf.find("s") {
+                Some(s) => try!(numbers(s, "s")),
+                // the last keyframe of newer exports only carries its time
+                None => continue
+            };
+            if from.is_empty() {
+                return Err(Invalid("s"));
+            }
+
+            if dims.is_empty() {
+                dims = from.iter().map(|&v| Curve::constant(v)).collect();
+            }
+
+            let next = match frames.get(n + 1) {
+                Some(next) => next,
+                None => break
+            };
+            let end = try!(number(try!(field(next, "t")), "t")) / fps;
+            // older exports store the end value in the keyframe itself,
+            // newer ones only as the start of the next keyframe
+            let to = match kf.find("e") {
+                Some(e) => try!(numbers(e, "e")),
+                None => match next.find("s") {
+                    Some(s) => try!(numbers(s, "s")),
+                    None => from.clone()
+                }
+            };
+            if to.is_empty() {
+                return Err(Invalid(if kf.find("e").is_some() {"e"} else {"s"}));
+            }
+            let hold = try!(flag(kf, "h"));
+            let out_tan = match kf.find("to") {
+                Some(v) => Some(try!(numbers(v, "to"))),
+                None => None
+            };
+            let in_tan = match kf.find("ti") {
+                Some(v) => Some(try!(numbers(v, "ti"))),
+                None => None
+            };
+
+            for (d, curve) in dims.iter_mut().enumerate() {
+                let a = from[cmp_min(d, from.len() - 1)];
+                let b = to[cmp_min(d, to.len() - 1)];
+                let ease = if hold {
+                    None
+                } else {
+                    let (ox, oy) = try!(handle(try!(field(kf, "o")), "o", d));
+                    let (ix, iy) = try!(handle(try!(field(kf, "i")), "i", d));
+                    Some(bezier(ox, oy, ix, iy))
+                };
+                let c1 = match out_tan {
+                    Some(ref t) if d < t.len() => a + t[d],
+                    _ => a + (b - a) / 3.
+                };
+                let c2 = match in_tan {
+                    Some(ref t) if d < t.len() => b + t[d],
+                    _ => b - (b - a) / 3.
+                };
+                curve.segments.push(Segment {
+                    start: start,
+                    end: end,
+                    from: a,
+                    c1: c1,
+                    c2: c2,
+                    to: b,
+                    ease: ease
+                });
+            }
+        }
+
+        Ok(Property {name: name.to_string(), dims: dims})
+    }
+}
+
+impl Layer {
+    /// Read a layer object.
+    pub fn from_json(j: &Json, fps: f64) -> LottieResult<Layer> {
+        let name = match j.find("nm").and_then(|n| n.as_string()) {
+            Some(n) => n.to_string(),
+            None => format!("{}", try!(field_or(j, "ind", 0.)))
+        };
+        let mut props = Vec::new();
+
+        match j.find("ks") {
+            Some(ks) => for &(key, prop_name) in TRANSFORM.iter() {
+                let p = match ks.find(key) {
+                    Some(p) => p,
+                    None => continue
+                };
+                // position may be split into separate x/y/z properties
+                if try!(flag(p, "s")) {
+                    let mut dims = Vec::new();
+                    for axis in ["x", "y", "z"].iter() {
+                        match p.find(*axis) {
+                            Some(sub) => {
+                                let sub = try!(Property::from_json(prop_name, sub, fps));
+                                dims.extend(sub.dims.into_iter());
+                            }
+                            None => {}
+                        }
+                    }
+                    props.push(Property {name: prop_name.to_string(), dims: dims});
+                } else {
+                    props.push(try!(Property::from_json(prop_name, p, fps)));
+                }
+            },
+            None => {}
+        }
+
+        let time_remap = match j.find("tm") {
+            Some(tm) => {
+                let p = try!(Property::from_json("time_remap", tm, fps));
+                p.dims.into_iter().next()
+            }
+            None => None
+        };
+
+        Ok(Layer {
+            name: name,
+            in_point: try!(field_or(j, "ip", 0.)) / fps,
+            out_point: try!(field_or(j, "op", 0.)) / fps,
+            start_time: try!(field_or(j, "st", 0.)) / fps,
+            properties: props,
+            time_remap: time_remap
+        })
+    }
+
+    /// Build a tween playing back all animated properties of this layer,
+    /// in composition time: it waits until the in point of the layer, and
+    /// plays the keyframes offset by its start time.
+    /// `bind` is asked for an accessor for every animated dimension,
+    /// e.g. `("Shape Layer 1.position", 0)`; returning `None` skips it.
+    pub fn tween<A: Access<f64> + Clone + 'static>
+    (&self, bind: |&str, uint| -> Option<A>) -> Option<Box<Tween + 'static>> {
+        // the tracks start playing at the in point; with a time remap they
+        // play source time, and the remap curve is shifted instead
+        let offset = match self.time_remap {
+            Some(_) => 0.,
+            None => self.start_time - self.in_point
+        };
+        let mut tracks = Vec::new();
+        for prop in self.properties.iter() {
+            let name = format!("{}.{}", self.name, prop.name);
+            for (d, curve) in prop.dims.iter().enumerate() {
+                if !curve.is_animated() {
+                    continue;
+                }
+                match bind(name.as_slice(), d) {
+                    Some(acc) => {
+                        let track = Track::new(acc, curve.shifted(offset));
+                        tracks.push(box track as Box<Tween + 'static>);
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        if tracks.is_empty() {
+            return None;
+        }
+        let tw = par(tracks);
+        let tw = match self.time_remap {
+            Some(ref remap) => {
+                let duration = self.out_point - self.in_point;
+                let remap = remap.shifted(self.start_time - self.in_point);
+                box TimeRemap::new(tw, remap, duration) as Box<Tween + 'static>
+            }
+            None => tw
+        };
+        Some(if self.in_point > 0. {delay(tw, self.in_point)} else {tw})
+    }
+}
+
+impl Animation {
+    /// Parse a Lottie JSON document.
+    pub fn from_str(s: &str) -> LottieResult<Animation> {
+        match json::from_str(s) {
+            Ok(j) => Animation::from_json(&j),
+            Err(e) => Err(Syntax(e))
+        }
+    }
+
+    /// Read an already parsed Lottie JSON document.
+    pub fn from_json(j: &Json) -> LottieResult<Animation> {
+        let fps = try!(number(try!(field(j, "fr")), "fr"));
+        if fps <= 0. {
+            return Err(Invalid("fr"));
+        }
+        let layers = try!(try!(field(j, "layers")).as_list().ok_or(Invalid("layers")));
+        let layers = try!(layers.iter().map(|l| Layer::from_json(l, fps)).collect());
+
+        Ok(Animation {
+            frame_rate: fps,
+            in_point: try!(field_or(j, "ip", 0.)) / fps,
+            out_point: try!(field_or(j, "op", 0.)) / fps,
+            layers: layers
+        })
+    }
+
+    /// The length of the composition in seconds.
+    pub fn duration(&self) -> f64 {
+        self.out_point - self.in_point
+    }
+
+    /// Build a tween playing back every layer, see `Layer::tween`.
+    pub fn tween<A: Access<f64> + Clone + 'static>
+    (&self, bind: |&str, uint| -> Option<A>) -> Box<Tween + 'static> {
+        let mut layers = Vec::new();
+        for layer in self.layers.iter() {
+            match layer.tween(|name, dim| bind(name, dim)) {
+                Some(tw) => layers.push(tw),
+                None => {}
+            }
+        }
+        par(layers)
+    }
+}
+
+/// A tween playing back a `Curve` through an accessor.
+#[deriving(Clone)]
+pub struct Track<A> {
+    acc: A,
+    curve: Curve,
+    current: f64,
+    duration: f64
+}
+
+impl<A: Access<f64>> Track<A> {
+    pub fn new(acc: A, curve: Curve) -> Track<A> {
+        // keyframes may all lie before the start
+        let duration = curve.duration().max(0.);
+        Track {
+            acc: acc,
+            curve: curve,
+            current: 0.,
+            duration: duration
+        }
+    }
+
+    /// How fast the value is changing, per second.
+    pub fn velocity(&self) -> f64 {
+        self.curve.velocity_at(self.current)
+    }
+}
+
+impl<A: Access<f64> + Clone + 'static> Tween for Track<A> {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        self.duration - self.current
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.current = 0.;
+    }
+
+    #[inline]
+    fn update(&mut self, delta: f64) -> f64 {
+        let remain = self.remaining();
+        self.current += if remain < delta {remain} else {delta};
+        let val = self.curve.value_at(self.current);
+        self.acc.set(val);
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("Track", self.current, self.remaining())
+    }
+}
+
+/// Plays a tween at the source time given by a time remap curve, instead
+/// of the time that has passed.
+#[deriving(Clone)]
+pub struct TimeRemap {
+    tween: Box<Tween + 'static>,
+    remap: Curve,
+    current: f64,
+    inner: f64,
+    duration: f64
+}
+
+impl TimeRemap {
+    pub fn new(tween: Box<Tween + 'static>, remap: Curve, duration: f64) -> TimeRemap {
+        TimeRemap {
+            tween: tween,
+            remap: remap,
+            current: 0.,
+            inner: 0.,
+            duration: duration
+        }
+    }
+}
+
+impl Tween for TimeRemap {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        self.duration - self.current
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.current = 0.;
+        self.inner = 0.;
+        self.tween.reset();
+    }
+
+    fn update(&mut self, delta: f64) -> f64 {
+        let remain = self.remaining();
+        self.current += if remain < delta {remain} else {delta};
+        let target = self.remap.value_at(self.current);
+        // tweens can only move forward, so going back in source time
+        // means replaying from the start
+        if target < self.inner {
+            self.tween.reset();
+            self.inner = 0.;
+        }
+        self.tween.update(target - self.inner);
+        self.inner = target;
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+        snap.push_f64(self.inner);
+        self.tween.save(snap);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+        self.inner = snap.f64();
+        self.tween.load(snap);
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("TimeRemap", self.current, self.remaining())
+            .children(vec![self.tween.describe()])
+    }
+}
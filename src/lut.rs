@@ -0,0 +1,271 @@
+//! Easings evaluated from tables of samples.
+//!
+//! `lut` precomputes any other easing, which pays off for the ones calling
+//! `powf` and `sin` when there are many tweens. `sampled` builds an easing
+//! from points drawn in a curve editor, and `sampled_monotonic` also checks
+//! that they never go backwards.
+
+use std::fmt;
+
+use ease::Ease;
+
+/// How to get from one sample to the next.
+#[deriving(Clone, PartialEq)]
+pub enum Interpolation {
+    /// Straight lines between samples. Cheapest, but the velocity jumps at
+    /// every sample.
+    Linear,
+    /// Smooth cubic curves between samples. Wherever the samples are
+    /// monotonic, so is the curve.
+    Cubic
+}
+
+/// A piecewise curve through samples `(xs[i], ys[i])`, with `ms[i]` being
+/// the tangents for `Cubic`.
+#[deriving(Clone)]
+struct Table {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    ms: Vec<f64>,
+    interp: Interpolation,
+    /// The xs are evenly spaced, so the segment can be found directly.
+    uniform: bool
+}
+
+impl Table {
+    fn new(xs: Vec<f64>, ys: Vec<f64>, interp: Interpolation, uniform: bool) -> Table {
+        let n = xs.len();
+        let ms = {
+            let secant = |i: uint| (ys[i + 1] - ys[i]) / (xs[i + 1] - xs[i]);
+
+            let mut ms = Vec::from_elem(n, 0f64);
+            if interp == Cubic && n > 1 {
+                ms[0] = secant(0);
+                ms[n - 1] = secant(n - 2);
+                for i in range(1, n - 1) {
+                    let (a, b) = (secant(i - 1), secant(i));
+                    // keep local extrema flat, so they don't overshoot
+                    ms[i] = if a * b <= 0. {0.} else {(a + b) / 2.};
+                }
+                // Fritsch-Carlson: limit tangents to keep monotonic parts so
+                for i in range(0, n - 1) {
+                    let d = secant(i);
+                    if d == 0. {
+                        ms[i] = 0.;
+                        ms[i + 1] = 0.;
+                        continue;
+                    }
+                    let a = ms[i] / d;
+                    let b = ms[i + 1] / d;
+                    let r = a * a + b * b;
+                    if r > 9. {
+                        let t = 3. / r.sqrt();
+                        ms[i] = t * a * d;
+                        ms[i + 1] = t * b * d;
+                    }
+                }
+            }
+            ms
+        };
+
+        Table {
+            xs: xs,
+            ys: ys,
+            ms: ms,
+            interp: interp,
+            uniform: uniform
+        }
+    }
+
+    /// The segment `t` is in, and how far into it, in `[0;1]`.
+    #[inline]
+    fn segment(&self, t: f64) -> (uint, f64) {
+        let n = self.xs.len();
+        let last = n - 2;
+        let i = if t <= self.xs[0] {
+            0
+        } else if t >= self.xs[n - 1] {
+            last
+        } else if self.uniform {
+            let i = ((t - self.xs[0]) / (self.xs[1] - self.xs[0])) as uint;
+            if i > last {last} else {i}
+        } else {
+            // binary search for the last x below t
+            let (mut lo, mut hi) = (0u, n - 1);
+            while hi - lo > 1 {
+                let mid = (lo + hi) / 2;
+                if self.xs[mid] <= t { lo = mid; } else { hi = mid; }
+            }
+            lo
+        };
+        let s = (t - self.xs[i]) / (self.xs[i + 1] - self.xs[i]);
+        (i, s.max(0.).min(1.))
+    }
+
+    fn eval(&self, t: f64) -> f64 {
+        let (i, s) = self.segment(t);
+        let (y0, y1) = (self.ys[i], self.ys[i + 1]);
+        match self.interp {
+            Linear => y0 + (y1 - y0) * s,
+            Cubic => {
+                let h = self.xs[i + 1] - self.xs[i];
+                let s2 = s * s;
+                let s3 = s2 * s;
+                (2. * s3 - 3. * s2 + 1.) * y0 + (s3 - 2. * s2 + s) * h * self.ms[i]
+                    + (-2. * s3 + 3. * s2) * y1 + (s3 - s2) * h * self.ms[i + 1]
+            }
+        }
+    }
+
+    /// The curve is held outside of the samples, so the slope there is 0.
+    fn slope(&self, t: f64) -> f64 {
+        if t < self.xs[0] || t > self.xs[self.xs.len() - 1] {
+            return 0.;
+        }
+        let (i, s) = self.segment(t);
+        let (y0, y1) = (self.ys[i], self.ys[i + 1]);
+        let h = self.xs[i + 1] - self.xs[i];
+        match self.interp {
+            Linear => (y1 - y0) / h,
+            Cubic => {
+                let s2 = s * s;
+                ((6. * s2 - 6. * s) * y0 + (3. * s2 - 4. * s + 1.) * h * self.ms[i]
+                    + (-6. * s2 + 6. * s) * y1 + (3. * s2 - 2. * s) * h * self.ms[i + 1]) / h
+            }
+        }
+    }
+}
+
+/// Another easing, precomputed for all modes.
+#[deriving(Clone)]
+pub struct LutEase {
+    ease_in: Table,
+    ease_out: Table,
+    ease_in_out: Table,
+    name: String
+}
+
+impl Ease for LutEase {
+    #[inline]
+    fn ease_in(&self, t: f64) -> f64 {
+        self.ease_in.eval(t)
+    }
+    #[inline]
+    fn ease_out(&self, t: f64) -> f64 {
+        self.ease_out.eval(t)
+    }
+    #[inline]
+    fn ease_in_out(&self, t: f64) -> f64 {
+        self.ease_in_out.eval(t)
+    }
+    #[inline]
+    fn velocity_in(&self, t: f64) -> f64 {
+        self.ease_in.slope(t)
+    }
+    #[inline]
+    fn velocity_out(&self, t: f64) -> f64 {
+        self.ease_out.slope(t)
+    }
+    #[inline]
+    fn velocity_in_out(&self, t: f64) -> f64 {
+        self.ease_in_out.slope(t)
+    }
+    fn name(&self) -> String {
+        format!("lut({})", self.name)
+    }
+}
+
+/// Precompute `ease` at `resolution` evenly spaced points, at least 2.
+/// A few hundred samples with `Cubic` are indistinguishable from the
+/// original for the built-in easings.
+pub fn lut<E: Ease>(ease: E, resolution: uint, interp: Interpolation) -> LutEase {
+    assert!(resolution >= 2, "a lookup table needs at least 2 samples");
+    let xs: Vec<f64> = range(0, resolution).map(|i| i as f64 / (resolution - 1) as f64).collect();
+    let table = |f: |f64| -> f64| {
+        let ys = xs.iter().map(|&x| f(x)).collect();
+        Table::new(xs.clone(), ys, interp.clone(), true)
+    };
+    LutEase {
+        ease_in: table(|t| ease.ease_in(t)),
+        ease_out: table(|t| ease.ease_out(t)),
+        ease_in_out: table(|t| ease.ease_in_out(t)),
+        name: ease.name()
+    }
+}
+
+/// Why sampled points can't be made into an easing.
+#[deriving(Clone, PartialEq)]
+pub enum SampleError {
+    /// Less than two points.
+    TooFew,
+    /// The point at this index doesn't come strictly after the one before,
+    /// so the curve wouldn't be a function of time.
+    Unsorted(uint),
+    /// The point at this index has its time outside of `[0;1]`.
+    OutOfRange(uint),
+    /// The point at this index has a lower alpha than the one before, see
+    /// `sampled_monotonic`.
+    Decreasing(uint)
+}
+
+impl fmt::Show for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TooFew => write!(f, "need at least two points"),
+            Unsorted(i) => write!(f, "point {} is not after the previous one", i),
+            OutOfRange(i) => write!(f, "point {} is outside of [0;1]", i),
+            Decreasing(i) => write!(f, "point {} is lower than the previous one", i)
+        }
+    }
+}
+
+/// An easing through points drawn by hand.
+#[deriving(Clone)]
+pub struct SampledEase {
+    table: Table
+}
+
+impl Ease for SampledEase {
+    #[inline]
+    fn ease_in(&self, t: f64) -> f64 {
+        self.table.eval(t)
+    }
+    #[inline]
+    fn velocity_in(&self, t: f64) -> f64 {
+        self.table.slope(t)
+    }
+    fn name(&self) -> String {
+        "sampled".to_string()
+    }
+}
+
+/// Build an easing from `(t, alpha)` points, sorted by `t` in `[0;1]`.
+/// Before the first and after the last point, the curve is held.
+pub fn sampled(points: &[(f64, f64)], interp: Interpolation) -> Result<SampledEase, SampleError> {
+    if points.len() < 2 {
+        return Err(TooFew);
+    }
+    for (i, &(x, _)) in points.iter().enumerate() {
+        if !(x >= 0. && x <= 1.) {
+            return Err(OutOfRange(i));
+        }
+        if i > 0 && x <= points[i - 1].val0() {
+            return Err(Unsorted(i));
+        }
+    }
+    let xs = points.iter().map(|&(x, _)| x).collect();
+    let ys = points.iter().map(|&(_, y)| y).collect();
+    Ok(SampledEase {table: Table::new(xs, ys, interp, false)})
+}
+
+/// Like `sampled`, but alpha must never decrease, so the curve never moves
+/// backwards. With `Cubic`, neither does the curve between the points.
+pub fn sampled_monotonic(points: &[(f64, f64)], interp: Interpolation) -> Result<SampledEase, SampleError> {
+    let ease = try!(sampled(points, interp));
+    for i in range(1, points.len()) {
+        if points[i].val1() < points[i - 1].val1() {
+            return Err(Decreasing(i));
+        }
+    }
+    Ok(ease)
+}
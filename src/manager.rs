@@ -0,0 +1,133 @@
+//! Owning many independent tweens and updating them together.
+
+use std::collections::TreeSet;
+
+use Tween;
+
+/// Identifies a tween added to a `Manager`.
+#[deriving(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Show)]
+pub struct TweenId(uint);
+
+/// How many removed ids are remembered, see `Manager::is_finished`.
+static REMEMBERED: uint = 256;
+
+struct Entry {
+    id: TweenId,
+    tween: Box<Tween + 'static>,
+    listeners: Vec<Sender<()>>
+}
+
+/// A collection of tweens that are updated by the same amount of time.
+/// Tweens are dropped once they are done.
+pub struct Manager {
+    next: uint,
+    entries: Vec<Entry>,
+    /// The ids of tweens removed before they were done, to tell them from
+    /// the finished ones.
+    removed: TreeSet<TweenId>,
+    /// Ids below this were pruned from `removed`, so nothing is known about
+    /// them anymore.
+    forgotten: uint
+}
+
+impl Manager {
+    pub fn new() -> Manager {
+        Manager {
+            next: 0,
+            entries: Vec::new(),
+            removed: TreeSet::new(),
+            forgotten: 0
+        }
+    }
+
+    /// Add a tween, it will be updated from the next `update` on.
+    pub fn add(&mut self, tween: Box<Tween + 'static>) -> TweenId {
+        let id = TweenId(self.next);
+        self.next += 1;
+        self.entries.push(Entry {
+            id: id,
+            tween: tween,
+            listeners: Vec::new()
+        });
+        id
+    }
+
+    /// Remove a tween before it is done. Anyone waiting for it to finish
+    /// sees the other end of the channel hang up.
+    pub fn remove(&mut self, id: TweenId) -> Option<Box<Tween + 'static>> {
+        match self.entries.iter().position(|e| e.id == id) {
+            Some(i) => {
+                self.removed.insert(id);
+                self.entries.remove(i).map(|e| e.tween)
+            }
+            None => None
+        }
+    }
+
+    /// Returns true if the tween is still running.
+    pub fn contains(&self, id: TweenId) -> bool {
+        self.entries.iter().any(|e| e.id == id)
+    }
+
+    /// Returns true if the tween ran until it was done. Only the last few
+    /// hundred removals are remembered, tweens added before the oldest of
+    /// those count as unknown.
+    pub fn is_finished(&self, id: TweenId) -> bool {
+        let TweenId(n) = id;
+        n >= self.forgotten && n < self.next && !self.contains(id) && !self.removed.contains(&id)
+    }
+
+    /// The number of running tweens.
+    pub fn len(&self) -> uint {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns a receiver that gets a `()` once the tween is done, right
+    /// away if it already is. If the tween is unknown or removed, the
+    /// sending end hangs up instead.
+    pub fn on_finish(&mut self, id: TweenId) -> Receiver<()> {
+        let (tx, rx) = channel();
+        if self.is_finished(id) {
+            tx.send(());
+            return rx;
+        }
+        match self.entries.iter_mut().find(|e| e.id == id) {
+            Some(e) => e.listeners.push(tx),
+            None => {}
+        }
+        rx
+    }
+
+    /// Update every tween by `delta`, in the order they were added, and
+    /// drop those that are done.
+    pub fn update(&mut self, delta: f64) {
+        for e in self.entries.iter_mut() {
+            e.tween.update(delta);
+        }
+
+        let mut i = 0;
+        while i < self.entries.len() {
+            if self.entries[i].tween.done() {
+                let e = self.entries.remove(i).unwrap();
+                for l in e.listeners.iter() {
+                    // nobody waiting anymore is fine
+                    let _ = l.send_opt(());
+                }
+            } else {
+                i += 1;
+            }
+        }
+
+        // forget the oldest removals
+        while self.removed.len() > REMEMBERED {
+            let oldest = *self.removed.iter().next().unwrap();
+            self.removed.remove(&oldest);
+            let TweenId(n) = oldest;
+            self.forgotten = n + 1;
+        }
+    }
+}
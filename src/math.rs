@@ -0,0 +1,261 @@
+//! The transcendental functions used by easings.
+//!
+//! By default these are the ones of the platform. With the `deterministic`
+//! feature they are computed in software instead, from additions,
+//! multiplications, divisions and square roots only. IEEE 754 requires those
+//! to be correctly rounded, so the results are the same on every machine,
+//! which lockstep multiplayer games rely on. They are accurate to a few
+//! units in the last place.
+
+#[cfg(feature = "deterministic")]
+pub use self::soft::{sin, cos, powf, powi, exp, ln, asin};
+
+#[inline]
+#[cfg(not(feature = "deterministic"))]
+pub fn sin(x: f64) -> f64 {
+    x.sin()
+}
+
+#[inline]
+#[cfg(not(feature = "deterministic"))]
+pub fn cos(x: f64) -> f64 {
+    x.cos()
+}
+
+#[inline]
+#[cfg(not(feature = "deterministic"))]
+pub fn powf(x: f64, y: f64) -> f64 {
+    x.powf(y)
+}
+
+#[inline]
+#[cfg(not(feature = "deterministic"))]
+pub fn powi(x: f64, n: i32) -> f64 {
+    x.powi(n)
+}
+
+#[inline]
+#[cfg(not(feature = "deterministic"))]
+pub fn exp(x: f64) -> f64 {
+    x.exp()
+}
+
+#[inline]
+#[cfg(not(feature = "deterministic"))]
+pub fn ln(x: f64) -> f64 {
+    x.ln()
+}
+
+#[inline]
+#[cfg(not(feature = "deterministic"))]
+pub fn asin(x: f64) -> f64 {
+    x.asin()
+}
+
+#[cfg(feature = "deterministic")]
+mod soft {
+    use std::mem;
+    use std::f64::{NAN, INFINITY, NEG_INFINITY};
+    use std::f64::consts::{FRAC_PI_2, FRAC_2_PI, LOG2_E, SQRT2};
+
+    // pi/2 and ln(2), split in a part with trailing zero bits, so that
+    // multiples of it are exact, and the rest
+    static PIO2_HI: f64 = 1.57079632673412561417e+00;
+    static PIO2_LO: f64 = 6.07710050650619224932e-11;
+    static LN2_HI: f64 = 6.93147180369123816490e-01;
+    static LN2_LO: f64 = 1.90821492927058770002e-10;
+
+    // Taylor series coefficients, from the second term on for sin and cos
+    static SIN: [f64, ..8] = [
+        -0.16666666666666666, 0.008333333333333333, -0.0001984126984126984,
+        2.7557319223985893e-06, -2.505210838544172e-08, 1.6059043836821613e-10,
+        -7.647163731819816e-13, 2.8114572543455206e-15];
+    static COS: [f64, ..8] = [
+        -0.5, 0.041666666666666664, -0.001388888888888889, 2.48015873015873e-05,
+        -2.755731922398589e-07, 2.08767569878681e-09, -1.1470745597729725e-11,
+        4.779477332387385e-14];
+    static EXP: [f64, ..14] = [
+        1.0, 1.0, 0.5, 0.16666666666666666, 0.041666666666666664,
+        0.008333333333333333, 0.001388888888888889, 0.0001984126984126984,
+        2.48015873015873e-05, 2.7557319223985893e-06, 2.755731922398589e-07,
+        2.505210838544172e-08, 2.08767569878681e-09, 1.6059043836821613e-10];
+    // 1 / (2k + 1), for ln(m) = 2 atanh((m - 1) / (m + 1))
+    static ATANH: [f64, ..11] = [
+        1.0, 0.3333333333333333, 0.2, 0.14285714285714285, 0.1111111111111111,
+        0.09090909090909091, 0.07692307692307693, 0.06666666666666667,
+        0.058823529411764705, 0.05263157894736842, 0.047619047619047616];
+    static ATAN: [f64, ..12] = [
+        1.0, -0.3333333333333333, 0.2, -0.14285714285714285, 0.1111111111111111,
+        -0.09090909090909091, 0.07692307692307693, -0.06666666666666667,
+        0.058823529411764705, -0.05263157894736842, 0.047619047619047616,
+        -0.043478260869565216];
+
+    /// Evaluate the polynomial with coefficients `c`, lowest first, at `z`.
+    #[inline]
+    fn poly(c: &[f64], z: f64) -> f64 {
+        let mut acc = c[c.len() - 1];
+        for i in range(0, c.len() - 1).rev() {
+            acc = acc * z + c[i];
+        }
+        acc
+    }
+
+    /// `2^k`, for `k` in the range of normal exponents.
+    #[inline]
+    fn pow2(k: i64) -> f64 {
+        unsafe { mem::transmute::<u64, f64>(((k + 1023) as u64) << 52) }
+    }
+
+    /// sin and cos for `|r| <= pi/4`.
+    #[inline]
+    fn sin_kernel(r: f64) -> f64 {
+        let z = r * r;
+        r + r * z * poly(&SIN, z)
+    }
+
+    #[inline]
+    fn cos_kernel(r: f64) -> f64 {
+        let z = r * r;
+        1. + z * poly(&COS, z)
+    }
+
+    /// Reduce `x` to `r` in `[-pi/4;pi/4]` and the quadrant it's in.
+    /// Exact enough as long as `|x|` is below about `1e6`.
+    #[inline]
+    fn reduce(x: f64) -> (f64, i64) {
+        let k = (x * FRAC_2_PI + 0.5).floor();
+        let r = (x - k * PIO2_HI) - k * PIO2_LO;
+        (r, (k as i64) & 3)
+    }
+
+    pub fn sin(x: f64) -> f64 {
+        if !x.is_finite() {
+            return NAN;
+        }
+        let (r, q) = reduce(x);
+        match q {
+            0 => sin_kernel(r),
+            1 => cos_kernel(r),
+            2 => -sin_kernel(r),
+            _ => -cos_kernel(r)
+        }
+    }
+
+    pub fn cos(x: f64) -> f64 {
+        if !x.is_finite() {
+            return NAN;
+        }
+        let (r, q) = reduce(x);
+        match q {
+            0 => cos_kernel(r),
+            1 => -sin_kernel(r),
+            2 => -cos_kernel(r),
+            _ => sin_kernel(r)
+        }
+    }
+
+    pub fn exp(x: f64) -> f64 {
+        if x != x {
+            return x;
+        }
+        if x > 709.782712893384 {
+            return INFINITY;
+        }
+        if x < -745.1332191019412 {
+            return 0.;
+        }
+        let k = (x * LOG2_E + 0.5).floor();
+        let r = (x - k * LN2_HI) - k * LN2_LO;
+        let p = poly(&EXP, r);
+        let k = k as i64;
+        if k < -1020 {
+            p * pow2(k + 1000) * pow2(-1000)
+        } else if k > 1023 {
+            p * pow2(k - 1) * 2.
+        } else {
+            p * pow2(k)
+        }
+    }
+
+    pub fn ln(x: f64) -> f64 {
+        if x != x || x < 0. {
+            return NAN;
+        }
+        if x == 0. {
+            return NEG_INFINITY;
+        }
+        if x == INFINITY {
+            return x;
+        }
+        let (x, shift) = if x < 2.2250738585072014e-308 {(x * pow2(54), 54)} else {(x, 0)};
+        let bits = unsafe { mem::transmute::<f64, u64>(x) };
+        let mut e = ((bits >> 52) & 0x7ff) as i64 - 1023 - shift;
+        // the mantissa, in [1;2)
+        let mut m = unsafe { mem::transmute::<u64, f64>((bits & 0x000f_ffff_ffff_ffff) | (1023 << 52)) };
+        if m > SQRT2 {
+            m = m * 0.5;
+            e += 1;
+        }
+        let s = (m - 1.) / (m + 1.);
+        let e = e as f64;
+        e * LN2_HI + (2. * s * poly(&ATANH, s * s) + e * LN2_LO)
+    }
+
+    pub fn powi(x: f64, n: i32) -> f64 {
+        let mut base = x;
+        let mut e = if n < 0 {-(n as i64)} else {n as i64};
+        let mut acc = 1.;
+        while e > 0 {
+            if e & 1 == 1 {
+                acc *= base;
+            }
+            base *= base;
+            e >>= 1;
+        }
+        if n < 0 {1. / acc} else {acc}
+    }
+
+    pub fn powf(x: f64, y: f64) -> f64 {
+        if y == 0. || x == 1. {
+            return 1.;
+        }
+        if x != x || y != y {
+            return NAN;
+        }
+        if x == 0. {
+            return if y > 0. {0.} else {INFINITY};
+        }
+        if x < 0. {
+            if y != y.floor() {
+                return NAN;
+            }
+            let r = exp(y * ln(-x));
+            return if (y * 0.5) != (y * 0.5).floor() {-r} else {r};
+        }
+        exp(y * ln(x))
+    }
+
+    /// atan for `0 <= a <= 1`.
+    fn atan_kernel(a: f64) -> f64 {
+        // halve the angle twice, down to at most pi/16
+        let a = a / (1. + (1. + a * a).sqrt());
+        let a = a / (1. + (1. + a * a).sqrt());
+        4. * (a * poly(&ATAN, a * a))
+    }
+
+    fn atan(x: f64) -> f64 {
+        let a = x.abs();
+        let r = if a > 1. {FRAC_PI_2 - atan_kernel(1. / a)} else {atan_kernel(a)};
+        if x < 0. {-r} else {r}
+    }
+
+    pub fn asin(x: f64) -> f64 {
+        if x != x || x.abs() > 1. {
+            return NAN;
+        }
+        if x.abs() == 1. {
+            return x * FRAC_PI_2;
+        }
+        atan(x / ((1. - x) * (1. + x)).sqrt())
+    }
+}
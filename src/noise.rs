@@ -0,0 +1,229 @@
+//! Procedural motion, like screen shake and idle wobble, from seeded noise.
+//!
+//! Noise tweens are additive: like `Relative`, they only add the change of
+//! their offset since the last update, so they can run on top of any other
+//! motion of the same property. When done, their offset is back at zero.
+
+use std::f64::INFINITY;
+
+use {Tween, Tweenable, Access, MulWithF64};
+use ease::{Ease, Mode, In, LinearEase, linear};
+use snapshot::{Snapshot, Reader};
+use describe::Node;
+
+/// The kind of noise a `Noise` tween follows. All of them stay in `[-1;1]`.
+#[deriving(Clone)]
+pub enum Kind {
+    /// Random values at whole steps, smoothly interpolated. Jittery,
+    /// good for shakes.
+    Value,
+    /// Gradient noise, smoother and rounder than `Value`.
+    Perlin,
+    /// A walk that takes a random step each time step, never leaving the
+    /// bounds. Drifts instead of oscillating around zero.
+    RandomWalk
+}
+
+/// A random number in `[-1;1]`, the same for the same inputs.
+#[inline]
+fn hash(seed: u32, axis: u32, i: i64) -> f64 {
+    let mut h = seed ^ (axis * 0x9E3779B9) ^ ((i as u32) * 0x85EBCA6B) ^ ((i >> 32) as u32);
+    h ^= h >> 16;
+    h *= 0x7FEB352D;
+    h ^= h >> 15;
+    h *= 0x846CA68B;
+    h ^= h >> 16;
+    h as f64 / 4294967295. * 2. - 1.
+}
+
+/// Smoothstep for value noise.
+#[inline]
+fn smooth(f: f64) -> f64 {
+    f * f * (3. - 2. * f)
+}
+
+/// Perlin's quintic fade curve.
+#[inline]
+fn fade(f: f64) -> f64 {
+    f * f * f * (f * (f * 6. - 15.) + 10.)
+}
+
+/// State of a random walk on one axis, between steps `index` and
+/// `index + 1`.
+#[deriving(Clone)]
+struct Walk {
+    index: i64,
+    at: f64,
+    next: f64
+}
+
+impl Walk {
+    fn new() -> Walk {
+        Walk {index: 0, at: 0., next: 0.}
+    }
+}
+
+/// A tween offsetting one or more axes by noise. The noise is sampled
+/// `frequency` times per time unit, scaled by `amplitude` and by an
+/// envelope that decays from 1 to 0 along an easing over the duration.
+#[deriving(Clone)]
+pub struct Noise<T, A, E> {
+    axes: Vec<A>,
+    applied: Vec<T>,
+    walks: Vec<Walk>,
+    kind: Kind,
+    seed: u32,
+    amplitude: T,
+    frequency: f64,
+    ease: E,
+    mode: Mode,
+    current: f64,
+    duration: f64
+}
+
+impl<T: Tweenable, A: Access<T>, E: Ease> Noise<T, A, E> {
+    pub fn new(kind: Kind, axes: Vec<A>, amplitude: T, frequency: f64, seed: u32,
+               ease: E, mode: Mode, duration: f64) -> Noise<T, A, E> {
+        let n = axes.len();
+        Noise {
+            axes: axes,
+            applied: Vec::from_elem(n, amplitude.mul_with_f64(0.)),
+            walks: Vec::from_elem(n, Walk::new()),
+            kind: kind,
+            seed: seed,
+            amplitude: amplitude,
+            frequency: frequency,
+            ease: ease,
+            mode: mode,
+            current: 0.,
+            duration: duration
+        }
+    }
+
+    /// The noise on `axis` at time `x`, measured in steps.
+    fn sample(&mut self, axis: uint, x: f64) -> f64 {
+        let i = x.floor() as i64;
+        let f = x - i as f64;
+        let ax = axis as u32;
+        match self.kind {
+            Value => {
+                let a = hash(self.seed, ax, i);
+                let b = hash(self.seed, ax, i + 1);
+                a + (b - a) * smooth(f)
+            }
+            Perlin => {
+                let a = hash(self.seed, ax, i) * f;
+                let b = hash(self.seed, ax, i + 1) * (f - 1.);
+                // 1D gradient noise peaks at 0.5, scale it up to 1
+                ((a + (b - a) * fade(f)) * 2.).max(-1.).min(1.)
+            }
+            RandomWalk => {
+                let seed = self.seed;
+                let w = &mut self.walks[axis];
+                if w.index > i {
+                    // every step depends on the ones before, so going back
+                    // means walking again from the start
+                    *w = Walk::new();
+                }
+                while w.index < i {
+                    w.index += 1;
+                    w.at = w.next;
+                    w.next = (w.at + hash(seed, ax, w.index) * 0.5).max(-1.).min(1.);
+                }
+                w.at + (w.next - w.at) * smooth(f)
+            }
+        }
+    }
+
+    /// The envelope at the current time. Without duration, it is the one
+    /// at the end, so there is no offset.
+    #[inline]
+    fn envelope(&self) -> f64 {
+        if self.duration == INFINITY {
+            return 1.;
+        }
+        if self.duration <= 0. {
+            return 0.;
+        }
+        1. - self.ease.ease(self.mode, self.current / self.duration)
+    }
+}
+
+impl<T: Tweenable + 'static, A: Access<T> + 'static, E: Ease + 'static> Tween for Noise<T, A, E> {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        self.duration - self.current
+    }
+
+    /// Keeps the current offset in the values, it is replaced from the
+    /// next update on.
+    #[inline]
+    fn reset(&mut self) {
+        self.current = 0.;
+        for w in self.walks.iter_mut() {
+            *w = Walk::new();
+        }
+    }
+
+    /// A negative `delta` moves back, but not before the start.
+    fn update(&mut self, delta: f64) -> f64 {
+        let remain = self.remaining();
+        self.current = (self.current + if remain < delta {remain} else {delta}).max(0.);
+        let env = self.envelope();
+        let x = self.current * self.frequency;
+        for axis in range(0, self.axes.len()) {
+            let n = self.sample(axis, x);
+            let offset = self.amplitude.mul_with_f64(n * env);
+            let val = self.axes[axis].get();
+            self.axes[axis].set(val + (offset - self.applied[axis]));
+            self.applied[axis] = offset;
+        }
+        delta - remain
+    }
+
+    /// Random walks are not saved, they are replayed from the start
+    /// after loading. They come out the same, since they only depend on
+    /// the seed.
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+        for &a in self.applied.iter() {
+            snap.push_value(a);
+        }
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+        for a in self.applied.iter_mut() {
+            *a = snap.value();
+        }
+        for w in self.walks.iter_mut() {
+            *w = Walk::new();
+        }
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("Noise", self.current, self.remaining())
+            .ease(format!("{} {}", self.ease.name(), self.mode))
+    }
+}
+
+/// Noise on the given axes, fading out along `ease` over `duration`.
+pub fn noise<T: Tweenable, A: Access<T>, E: Ease>
+(kind: Kind, axes: Vec<A>, amplitude: T, frequency: f64, seed: u32, ease: E, mode: Mode, duration: f64)
+-> Noise<T, A, E> {
+    Noise::new(kind, axes, amplitude, frequency, seed, ease, mode, duration)
+}
+
+/// A shake that starts at full `amplitude` and dies down along `ease`.
+pub fn shake<T: Tweenable, A: Access<T>, E: Ease>
+(axes: Vec<A>, amplitude: T, frequency: f64, seed: u32, ease: E, duration: f64)
+-> Noise<T, A, E> {
+    Noise::new(Value, axes, amplitude, frequency, seed, ease, In, duration)
+}
+
+/// A smooth wobble at constant amplitude that never ends.
+pub fn wiggle<T: Tweenable, A: Access<T>>
+(axes: Vec<A>, amplitude: T, frequency: f64, seed: u32)
+-> Noise<T, A, LinearEase> {
+    Noise::new(Perlin, axes, amplitude, frequency, seed, linear(), In, INFINITY)
+}
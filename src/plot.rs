@@ -0,0 +1,323 @@
+//! Drawing tween trees as SVG, e.g. to attach to a pull request or to look
+//! at the timing of a tree that misbehaves.
+//!
+//! `gantt` draws a bar for every tween of a tree, built from its
+//! `describe` output: children of a `Sequence` one after the other, those
+//! of a `Parallel` below each other, and every cycle of a `Repeat`. Pauses
+//! are gray, `Exec` calls are diamonds, and a line marks how far the tree
+//! has played. `chart` adds a lane for every target, with its value curve
+//! sampled by playing a clone of the tree, see `sample` for what that
+//! means for the rest of the program:
+//!
+//! ```rust
+//! let svg = chart(&tw, vec![("x", x.clone()), ("y", y.clone())], &Style::new());
+//! File::create(&Path::new("timing.svg")).write_str(svg.as_slice()).unwrap();
+//! ```
+
+use std::f64::INFINITY;
+
+use {Tween, Tweenable, Access};
+use describe::Node;
+
+/// The colors of the value curves, in the order of the targets.
+static COLORS: [&'static str, ..6] = ["#4c78a8", "#f58518", "#54a24b", "#e45756", "#b279a2", "#72b7b2"];
+
+/// Sizes of a chart, in pixels.
+#[deriving(Clone)]
+pub struct Style {
+    pub width: f64,
+    /// The height of one bar of the Gantt chart.
+    pub row: f64,
+    /// The height of the lane of one value curve.
+    pub lane: f64,
+    /// The time shown. By default the duration of the tree, or four cycles
+    /// of every repeat in it.
+    pub window: Option<f64>,
+    /// The number of samples of the value curves.
+    pub samples: uint
+}
+
+impl Style {
+    pub fn new() -> Style {
+        Style {
+            width: 800.,
+            row: 18.,
+            lane: 80.,
+            window: None,
+            samples: 400
+        }
+    }
+}
+
+/// Room for the labels on the left, and around the plot.
+static LEFT: f64 = 260.;
+static PAD: f64 = 10.;
+static AXIS: f64 = 24.;
+
+/// One row of the Gantt chart: a tween, and where each of its plays starts.
+struct Row {
+    depth: uint,
+    node: Node,
+    duration: f64,
+    starts: Vec<f64>
+}
+
+fn duration(node: &Node) -> f64 {
+    node.elapsed + node.remaining
+}
+
+/// The time needed to show a node, with four cycles of every repeat.
+fn extent(node: &Node) -> f64 {
+    let d = duration(node);
+    if d.is_finite() {
+        return d;
+    }
+    match node.kind {
+        "Repeat" => node.children.iter().fold(0., |a, c| a + extent(c)) * 4.,
+        "Sequence" => node.children.iter().fold(0., |a, c| a + extent(c)),
+        _ => node.children.iter().fold(0., |a, c| a.max(extent(c)))
+    }
+}
+
+fn layout(node: &Node, starts: Vec<f64>, depth: uint, end: f64, rows: &mut Vec<Row>) {
+    let d = duration(node);
+    rows.push(Row {depth: depth, node: node.clone(), duration: d, starts: starts.clone()});
+
+    match node.kind {
+        "Sequence" => {
+            let mut offset = 0.;
+            for c in node.children.iter() {
+                let s = starts.iter().map(|&s| s + offset).filter(|&s| s <= end).collect();
+                layout(c, s, depth + 1, end, rows);
+                offset += duration(c);
+            }
+        }
+        "Repeat" => {
+            for c in node.children.iter() {
+                let cycle = duration(c);
+                let mut s = Vec::new();
+                for &start in starts.iter() {
+                    let mut t = start;
+                    loop {
+                        s.push(t);
+                        t += cycle;
+                        if cycle <= 0. || t >= end || t >= start + d {
+                            break;
+                        }
+                    }
+                }
+                layout(c, s, depth + 1, end, rows);
+            }
+        }
+        _ => {
+            for c in node.children.iter() {
+                layout(c, starts.clone(), depth + 1, end, rows);
+            }
+        }
+    }
+}
+
+/// Escape text for SVG.
+fn escape(s: &str) -> String {
+    s.replace("&", "&amp;").replace("<", "&lt;").replace(">", "&gt;").replace("\"", "&quot;")
+}
+
+fn fill(kind: &str) -> &'static str {
+    match kind {
+        "Pause" => "#d9d9d9",
+        "Exec" => "#e45756",
+        "Sequence" | "Parallel" | "Repeat" | "Reverse" | "Blend" | "Concurrent" | "TimeRemap" => "#dbe6f1",
+        _ => "#4c78a8"
+    }
+}
+
+/// A step for the time axis of about a tenth of `window`, of 1, 2 or 5
+/// times a power of ten.
+fn tick_step(window: f64) -> f64 {
+    let raw = window / 10.;
+    let mag = 10f64.powf(raw.log10().floor());
+    let n = raw / mag;
+    mag * if n < 1.5 {1.} else if n < 3.5 {2.} else if n < 7.5 {5.} else {10.}
+}
+
+/// Draws into an SVG string, mapping time to x.
+struct Canvas {
+    out: String,
+    scale: f64
+}
+
+impl Canvas {
+    fn x(&self, time: f64) -> f64 {
+        LEFT + time * self.scale
+    }
+
+    fn push(&mut self, s: String) {
+        self.out.push_str(s.as_slice());
+        self.out.push('\n');
+    }
+
+    fn axis(&mut self, window: f64, y: f64, height: f64) {
+        let step = tick_step(window);
+        let mut t = 0.;
+        while t <= window + step * 1e-6 {
+            let x = self.x(t);
+            self.push(format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#eeeeee\"/>",
+                              x, y, x, y + height));
+            self.push(format!("<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#666666\">{}</text>",
+                              x, y - 6., t));
+            t += step;
+        }
+    }
+
+    fn rows(&mut self, rows: &[Row], y: f64, row: f64, end: f64) {
+        for (i, r) in rows.iter().enumerate() {
+            let top = y + i as f64 * row;
+            let mut label = String::new();
+            for _ in range(0, r.depth) {
+                label.push_str("  ");
+            }
+            label.push_str(r.node.kind);
+            match r.node.label {
+                Some(l) => label.push_str(format!(" \"{}\"", l).as_slice()),
+                None => {}
+            }
+            match r.node.ease {
+                Some(ref e) => label.push_str(format!(" {}", e).as_slice()),
+                None => {}
+            }
+            match r.node.repeats {
+                Some(n) => label.push_str(format!(" x{}", n).as_slice()),
+                None => {}
+            }
+            self.push(format!("<text x=\"{}\" y=\"{}\" xml:space=\"preserve\">{}</text>",
+                              PAD, top + row * 0.75, escape(label.as_slice())));
+
+            for &start in r.starts.iter() {
+                let x = self.x(start);
+                if r.node.kind == "Exec" {
+                    let (cy, h) = (top + row / 2., row * 0.375);
+                    self.push(format!("<polygon points=\"{},{} {},{} {},{} {},{}\" fill=\"{}\"/>",
+                                      x, cy - h, x + h, cy, x, cy + h, x - h, cy, fill(r.node.kind)));
+                } else {
+                    let stop = if r.duration.is_finite() {(start + r.duration).min(end)} else {end};
+                    self.push(format!("<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#ffffff\"/>",
+                                      x, top + 2., (self.x(stop) - x).max(1.), row - 4., fill(r.node.kind)));
+                }
+            }
+        }
+    }
+
+    fn playhead(&mut self, time: f64, y: f64, height: f64) {
+        let x = self.x(time);
+        self.push(format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#e45756\" stroke-dasharray=\"4,3\"/>",
+                          x, y, x, y + height));
+    }
+
+    fn curve(&mut self, name: &str, color: &str, samples: &[(f64, f64)], y: f64, lane: f64) {
+        let lo = samples.iter().fold(INFINITY, |a, &(_, v)| a.min(v));
+        let hi = samples.iter().fold(-INFINITY, |a, &(_, v)| a.max(v));
+        let span = if hi > lo {hi - lo} else {1.};
+        let to_y = |v: f64| y + lane - 4. - (v - lo) / span * (lane - 8.);
+
+        self.push(format!("<text x=\"{}\" y=\"{}\" fill=\"{}\">{}</text>", PAD, y + 14., color, escape(name)));
+        self.push(format!("<text x=\"{}\" y=\"{}\" fill=\"#666666\">{} .. {}</text>", PAD, y + 30., lo, hi));
+        self.push(format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#cccccc\"/>",
+                          LEFT, y + lane, self.x(samples.last().map_or(0., |&(t, _)| t)), y + lane));
+        let points: Vec<String> = samples.iter()
+            .map(|&(t, v)| format!("{:.2},{:.2}", self.x(t), to_y(v)))
+            .collect();
+        self.push(format!("<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>",
+                          points.connect(" "), color));
+    }
+}
+
+fn header(width: f64, height: f64) -> String {
+    format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             font-family=\"monospace\" font-size=\"11\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n", width, height)
+}
+
+fn window(root: &Node, style: &Style) -> f64 {
+    match style.window {
+        Some(w) => w,
+        None => {
+            let w = extent(root);
+            if w > 0. {w} else {1.}
+        }
+    }
+}
+
+/// Draw a Gantt chart of a tree, see the module documentation.
+pub fn gantt(root: &Node, style: &Style) -> String {
+    let end = window(root, style);
+    let mut rows = Vec::new();
+    layout(root, vec![0.], 0, end, &mut rows);
+
+    let height = AXIS + rows.len() as f64 * style.row + PAD;
+    let mut c = Canvas {out: header(style.width, height), scale: (style.width - LEFT - PAD) / end};
+    c.axis(end, AXIS, rows.len() as f64 * style.row);
+    c.rows(rows.as_slice(), AXIS, style.row, end);
+    c.playhead(root.elapsed.min(end), AXIS, rows.len() as f64 * style.row);
+    c.out.push_str("</svg>\n");
+    c.out
+}
+
+/// Play a clone of `tween` from the start for `duration`, reading every
+/// target at the start and `samples` times after. Returns `(time, value)`
+/// pairs for every target. Nothing has been written at the start yet, so
+/// the first value is the one the target had before.
+///
+/// The clone writes through the same accessors as `tween`, so this has
+/// side effects: `Exec` functions are called, and every property the tree
+/// writes to is changed. Only `targets` are set back to their values from
+/// before afterwards.
+pub fn sample<T: Tweenable, A: Access<T>>
+(tween: &Box<Tween + 'static>, targets: &mut [A], duration: f64, samples: uint) -> Vec<Vec<(f64, f64)>> {
+    let before: Vec<T> = targets.iter().map(|t| t.get()).collect();
+    let mut tw = tween.clone();
+    tw.reset();
+
+    let step = duration / samples as f64;
+    let mut out: Vec<Vec<(f64, f64)>> = targets.iter().map(|_| Vec::with_capacity(samples + 1)).collect();
+    for i in range(0, samples + 1) {
+        if i > 0 {
+            tw.update(step);
+        }
+        for (j, t) in targets.iter().enumerate() {
+            out[j].push((i as f64 * step, t.get().to_f64().unwrap()));
+        }
+    }
+
+    for (t, &v) in targets.iter_mut().zip(before.iter()) {
+        t.set(v);
+    }
+    out
+}
+
+/// Draw a Gantt chart of `tween`, with the value curve of every named
+/// target below it. The curves are sampled with `sample`, with the same
+/// side effects.
+pub fn chart<T: Tweenable, A: Access<T>>
+(tween: &Box<Tween + 'static>, targets: Vec<(&str, A)>, style: &Style) -> String {
+    let root = tween.describe();
+    let end = window(&root, style);
+    let mut rows = Vec::new();
+    layout(&root, vec![0.], 0, end, &mut rows);
+
+    let names: Vec<&str> = targets.iter().map(|&(n, _)| n).collect();
+    let mut accs: Vec<A> = targets.into_iter().map(|(_, a)| a).collect();
+    let curves = sample(tween, accs.as_mut_slice(), end, style.samples);
+
+    let bars = rows.len() as f64 * style.row;
+    let height = AXIS + bars + curves.len() as f64 * (style.lane + PAD) + PAD;
+    let mut c = Canvas {out: header(style.width, height), scale: (style.width - LEFT - PAD) / end};
+    c.axis(end, AXIS, height - AXIS - PAD);
+    c.rows(rows.as_slice(), AXIS, style.row, end);
+    for (i, samples) in curves.iter().enumerate() {
+        let y = AXIS + bars + PAD + i as f64 * (style.lane + PAD);
+        c.curve(names[i], COLORS[i % COLORS.len()], samples.as_slice(), y, style.lane);
+    }
+    c.playhead(root.elapsed.min(end), AXIS, height - AXIS - PAD);
+    c.out.push_str("</svg>\n");
+    c.out
+}
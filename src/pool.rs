@@ -0,0 +1,277 @@
+//! Updating the children of a parallel tween on several tasks at once.
+//! Only available with the `threads` feature.
+//!
+//! Children are moved to the worker tasks of a `Pool` for every update and
+//! moved back afterwards, so they have to be `Send`, e.g. `Single`s using
+//! the accessors from `sync`, or trees of them built with `send_seq`,
+//! `send_rep` and `send_exec`. Functions run by `Exec` tweens on a worker
+//! are not called there, but queued and called on the updating task
+//! afterwards, in the order a plain `Parallel` would have called them. As
+//! long as the children don't share properties, the results are the same
+//! as with `Parallel`.
+//!
+//! `Parallel` and `Manager` themselves stay single threaded, as the tweens
+//! they own don't have to be `Send`. Use a `Concurrent` in place of a
+//! `Parallel`, or add one to a `Manager` as a group of tweens updated on
+//! the pool.
+//!
+//! If a tween panics on a worker, updating panics as well. The tweens
+//! given to that update are lost.
+
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+
+use {Tween, Sequence, Repeat, Exec};
+use snapshot::{Snapshot, Reader};
+use describe::Node;
+
+local_data_key!(QUEUE: RefCell<Vec<fn()>>)
+
+/// Call an `Exec` function, or queue it if a worker is updating.
+pub fn run(f: fn()) {
+    match QUEUE.get() {
+        Some(q) => q.borrow_mut().push(f),
+        None => f()
+    }
+}
+
+/// A tween that can be moved to another task.
+pub trait SendTween: Tween + Send {
+    /// Same deal as `Tween::clone_into_box`, but keeping `Send`.
+    fn clone_send(&self) -> Box<SendTween + Send>;
+}
+
+impl<T: Tween + Send> SendTween for T {
+    fn clone_send(&self) -> Box<SendTween + Send> {
+        box self.clone() as Box<SendTween + Send>
+    }
+}
+
+impl Clone for Box<SendTween + Send> {
+    #[inline]
+    fn clone(&self) -> Box<SendTween + Send> {
+        (**self).clone_send()
+    }
+}
+
+/// Like a `Box<Tween>`, so `Sequence`s and `Repeat`s can hold these.
+impl Tween for Box<SendTween + Send> {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        (**self).remaining()
+    }
+
+    #[inline]
+    fn done(&self) -> bool {
+        (**self).done()
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    #[inline]
+    fn update(&mut self, delta: f64) -> f64 {
+        (**self).update(delta)
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        (**self).save(snap)
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        (**self).load(snap)
+    }
+
+    fn describe(&self) -> Node {
+        (**self).describe()
+    }
+
+    fn clone_into_box<'a>(&self) -> Box<Tween + 'a> {
+        (**self).clone_into_box()
+    }
+}
+
+/// A chunk of tweens, its position among all chunks, the time to update
+/// them by, whether to skip the finished ones, and where to send them back.
+type Job = (uint, Vec<Box<SendTween + Send>>, f64, bool, Sender<Done>);
+
+/// A chunk of updated tweens, and the functions each of them queued.
+type Done = (uint, Vec<Box<SendTween + Send>>, Vec<Vec<fn()>>);
+
+/// A fixed number of worker tasks. They quit when the pool is dropped.
+pub struct Pool {
+    workers: Vec<Sender<Job>>
+}
+
+impl Pool {
+    /// Spawn `n` workers, at least one.
+    pub fn new(n: uint) -> Pool {
+        assert!(n >= 1, "a pool needs at least one worker");
+        let workers = range(0, n).map(|_| {
+            let (tx, jobs) = channel::<Job>();
+            spawn(proc() {
+                for (chunk, mut tweens, delta, skip_done, done_tx) in jobs.iter() {
+                    let mut calls = Vec::with_capacity(tweens.len());
+                    for tw in tweens.iter_mut() {
+                        if skip_done && delta >= 0. && tw.done() {
+                            calls.push(Vec::new());
+                            continue;
+                        }
+                        QUEUE.replace(Some(RefCell::new(Vec::new())));
+                        let remain = tw.remaining();
+                        tw.update(if remain < delta {remain} else {delta});
+                        calls.push(QUEUE.replace(None).unwrap().unwrap());
+                    }
+                    let _ = done_tx.send_opt((chunk, tweens, calls));
+                }
+            });
+            tx
+        }).collect();
+
+        Pool {
+            workers: workers
+        }
+    }
+
+    /// Update all `tweens` by `delta`, split evenly among the workers, and
+    /// call the functions they queued in order. With `skip_done`, tweens
+    /// that are done are left alone unless going backwards, like in a
+    /// `Parallel` after its first update. Returns the tweens in the order
+    /// they were given.
+    pub fn update(&self, tweens: Vec<Box<SendTween + Send>>, delta: f64, skip_done: bool)
+    -> Vec<Box<SendTween + Send>> {
+        let n = tweens.len();
+        let size = (n + self.workers.len() - 1) / self.workers.len();
+        if size == 0 {
+            return tweens;
+        }
+
+        // a channel per update, so a worker that panics hangs up on it
+        let (done_tx, results) = channel();
+        let mut chunks = 0;
+        let mut iter = tweens.into_iter();
+        loop {
+            let chunk: Vec<Box<SendTween + Send>> = iter.by_ref().take(size).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            if self.workers[chunks].send_opt((chunks, chunk, delta, skip_done, done_tx.clone())).is_err() {
+                panic!("a worker of the pool has died");
+            }
+            chunks += 1;
+        }
+        drop(done_tx);
+
+        let mut done: Vec<Option<(Vec<Box<SendTween + Send>>, Vec<Vec<fn()>>)>> =
+            Vec::from_fn(chunks, |_| None);
+        for _ in range(0, chunks) {
+            match results.recv_opt() {
+                Ok((i, tweens, calls)) => done[i] = Some((tweens, calls)),
+                Err(()) => panic!("a tween panicked on a worker of the pool")
+            }
+        }
+
+        let mut out = Vec::with_capacity(n);
+        for d in done.into_iter() {
+            let (tweens, calls) = d.unwrap();
+            for c in calls.iter() {
+                for &f in c.iter() {
+                    run(f);
+                }
+            }
+            out.extend(tweens.into_iter());
+        }
+        out
+    }
+}
+
+/// Like `Parallel`, but updating its children on the workers of a pool.
+#[deriving(Clone)]
+pub struct Concurrent {
+    tweens: Vec<Box<SendTween + Send>>,
+    pool: Rc<Pool>,
+    /// Whether it was updated since the last reset.
+    started: bool
+}
+
+impl Concurrent {
+    pub fn new(pool: Rc<Pool>, tweens: Vec<Box<SendTween + Send>>) -> Concurrent {
+        Concurrent {
+            tweens: tweens,
+            pool: pool,
+            started: false
+        }
+    }
+}
+
+impl Tween for Concurrent {
+    /// The max remaining time of all wrapped tweens
+    #[inline]
+    fn remaining(&self) -> f64 {
+        self.tweens.iter().fold(0., |a, t| {
+            let r = t.remaining();
+            if r > a {r} else {a}
+        })
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.started = false;
+        for tw in self.tweens.iter_mut() {
+            tw.reset();
+        }
+    }
+
+    fn update(&mut self, delta: f64) -> f64 {
+        let remain = self.remaining();
+        let tweens = mem::replace(&mut self.tweens, Vec::new());
+        self.tweens = self.pool.update(tweens, delta, self.started);
+        self.started = true;
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_bool(self.started);
+        for tw in self.tweens.iter() {
+            tw.save(snap);
+        }
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.started = snap.bool();
+        for tw in self.tweens.iter_mut() {
+            tw.load(snap);
+        }
+    }
+
+    fn describe(&self) -> Node {
+        let children: Vec<Node> = self.tweens.iter().map(|tw| tw.describe()).collect();
+        let elapsed = children.iter().fold(0., |a, c| if c.elapsed > a {c.elapsed} else {a});
+        Node::new("Concurrent", elapsed, self.remaining())
+            .children(children)
+    }
+}
+
+/// Returns a tween that updates the given tweens in parallel, on the
+/// workers of `pool`.
+pub fn concurrent(pool: Rc<Pool>, tweens: Vec<Box<SendTween + Send>>) -> Box<Tween + 'static> {
+    box Concurrent::new(pool, tweens) as Box<Tween + 'static>
+}
+
+/// Like `exec`, but `Send`, to be updated on a pool.
+pub fn send_exec(content: fn()) -> Box<SendTween + Send> {
+    box Exec::new(content) as Box<SendTween + Send>
+}
+
+/// Like `seq`, but `Send`, to be updated on a pool.
+pub fn send_seq(tweens: Vec<Box<SendTween + Send>>) -> Box<SendTween + Send> {
+    box Sequence::new(tweens) as Box<SendTween + Send>
+}
+
+/// Like `rep`, but `Send`, to be updated on a pool.
+pub fn send_rep(tween: Box<SendTween + Send>) -> Box<SendTween + Send> {
+    box Repeat::new(tween) as Box<SendTween + Send>
+}
@@ -0,0 +1,136 @@
+//! Tweening several properties along one timeline.
+//!
+//! Instead of a `Parallel` of one `Single` per property, a `MultiProperty`
+//! evaluates its easing once per update and interpolates every property
+//! with the same alpha. The properties may be of different types, and use
+//! different accessors.
+//!
+//! ```rust
+//! // x and y are `Rc<Cell<f64>>`, alpha is `Rc<Cell<f32>>`
+//! let tw = to_props!(quint(), InOut, 2., [
+//!     x.clone() => 100.,
+//!     y.clone() => 50.,
+//!     alpha.clone() => 0f32
+//! ]);
+//! ```
+
+use {Tween, Tweenable, Access, Lerp};
+use ease::{Ease, Mode};
+use snapshot::{Snapshot, Reader};
+use describe::Node;
+
+/// One property of a `MultiProperty`, with its bounds.
+pub trait Prop {
+    /// Set the property to the interpolation between its bounds at `alpha`.
+    fn apply(&mut self, alpha: f64);
+
+    /// Same deal as `Tween::clone_into_box`.
+    fn clone_box(&self) -> Box<Prop + 'static>;
+}
+
+impl Clone for Box<Prop + 'static> {
+    #[inline]
+    fn clone(&self) -> Box<Prop + 'static> {
+        self.clone_box()
+    }
+}
+
+#[deriving(Clone)]
+struct Bounds<T, A> {
+    acc: A,
+    start: T,
+    end: T
+}
+
+impl<T: Tweenable + 'static, A: Access<T> + 'static> Prop for Bounds<T, A> {
+    #[inline]
+    fn apply(&mut self, alpha: f64) {
+        let new = self.start.lerp(&self.start, &self.end, alpha);
+        self.acc.set(new);
+    }
+
+    fn clone_box(&self) -> Box<Prop + 'static> {
+        box self.clone() as Box<Prop + 'static>
+    }
+}
+
+/// A property going from `start` to `end`.
+pub fn prop<T: Tweenable + 'static, A: Access<T> + 'static>(acc: A, start: T, end: T) -> Box<Prop + 'static> {
+    box Bounds {acc: acc, start: start, end: end} as Box<Prop + 'static>
+}
+
+/// A property going from its current value to `end`.
+pub fn to_prop<T: Tweenable + 'static, A: Access<T> + 'static>(acc: A, end: T) -> Box<Prop + 'static> {
+    let start = acc.get();
+    prop(acc, start, end)
+}
+
+/// Several properties tweened with the same easing, mode and duration.
+#[deriving(Clone)]
+pub struct MultiProperty<E> {
+    props: Vec<Box<Prop + 'static>>,
+    ease: E,
+    mode: Mode,
+    current: f64,
+    duration: f64
+}
+
+impl<E: Ease> MultiProperty<E> {
+    pub fn new(props: Vec<Box<Prop + 'static>>, ease: E, mode: Mode, duration: f64) -> MultiProperty<E> {
+        MultiProperty {
+            props: props,
+            ease: ease,
+            mode: mode,
+            current: 0.,
+            duration: duration
+        }
+    }
+}
+
+impl<E: Ease + 'static> Tween for MultiProperty<E> {
+    #[inline]
+    fn remaining(&self) -> f64 {
+        self.duration - self.current
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.current = 0.;
+    }
+
+    #[inline]
+    fn update(&mut self, delta: f64) -> f64 {
+        let remain = self.remaining();
+        self.current += if remain < delta {remain} else {delta};
+        let t = if self.duration > 0. {self.current / self.duration} else {1.};
+        let a = self.ease.ease(self.mode, t);
+        for p in self.props.iter_mut() {
+            p.apply(a);
+        }
+        delta - remain
+    }
+
+    fn save(&self, snap: &mut Snapshot) {
+        snap.push_f64(self.current);
+    }
+
+    fn load(&mut self, snap: &mut Reader) {
+        self.current = snap.f64();
+    }
+
+    fn describe(&self) -> Node {
+        Node::new("MultiProperty", self.current, self.remaining())
+            .ease(format!("{} {}", self.ease.name(), self.mode))
+    }
+}
+
+/// Tween several properties from their current values to the given ones,
+/// with one easing, mode and duration.
+#[macro_export]
+macro_rules! to_props(
+    ($ease:expr, $mode:expr, $duration:expr, [$($acc:expr => $end:expr),+]) => (
+        ::tween::props::MultiProperty::new(
+            vec![$(::tween::props::to_prop($acc, $end)),+],
+            $ease, $mode, $duration)
+    )
+)
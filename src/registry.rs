@@ -0,0 +1,157 @@
+//! Looking up easings by name, for animations described in data files.
+//!
+//! Understood are registered names like `"quint"`, the Penner style names
+//! built from them like `"easeInOutQuint"`, the CSS keywords `"linear"`,
+//! `"ease"`, `"ease-in"`, `"ease-out"`, `"ease-in-out"`, `"step-start"` and
+//! `"step-end"`, CSS `"cubic-bezier(x1, y1, x2, y2)"` and
+//! `"steps(n, start|end)"`, and the parameterized easings `"power(p)"`,
+//! `"back(s)"`, `"elastic(a)"`, `"elastic(a, p)"`, `"bounce(n)"` and
+//! `"bounce(n, r)"`. A bounce takes at most `MAX_BOUNCES` bounces.
+
+use std::collections::HashMap;
+
+use ease;
+use ease::{DynEase, Mode, In, Out, InOut, OutIn, boxed};
+
+/// The most bounces `"bounce(n)"` accepts. Every bounce makes the easing
+/// slower, and the later ones are too small to see anyway.
+pub static MAX_BOUNCES: uint = 100;
+
+/// Maps names to easings.
+pub struct Registry {
+    entries: HashMap<String, Box<DynEase + 'static>>
+}
+
+/// Lowercase, for case insensitive lookups.
+fn lower(s: &str) -> String {
+    s.chars().map(|c| c.to_lowercase()).collect()
+}
+
+impl Registry {
+    /// A registry without any names.
+    pub fn new() -> Registry {
+        Registry {entries: HashMap::new()}
+    }
+
+    /// A registry knowing all easings of this library and the CSS keywords.
+    pub fn with_builtins() -> Registry {
+        let mut r = Registry::new();
+        r.register("linear", boxed(ease::linear()));
+        r.register("quad", boxed(ease::quad()));
+        r.register("cubic", boxed(ease::cubic()));
+        r.register("quart", boxed(ease::quart()));
+        r.register("quint", boxed(ease::quint()));
+        r.register("sine", boxed(ease::sine()));
+        r.register("circ", boxed(ease::circ()));
+        r.register("back", boxed(ease::back()));
+        r.register("elastic", boxed(ease::elastic()));
+        r.register("bounce", boxed(ease::bounce()));
+
+        r.register("ease", boxed(ease::bezier(0.25, 0.1, 0.25, 1.)));
+        r.register("ease-in", boxed(ease::bezier(0.42, 0., 1., 1.)));
+        r.register("ease-out", boxed(ease::bezier(0., 0., 0.58, 1.)));
+        r.register("ease-in-out", boxed(ease::bezier(0.42, 0., 0.58, 1.)));
+        r.register("step-start", boxed(ease::steps(1).start()));
+        r.register("step-end", boxed(ease::steps(1)));
+        r
+    }
+
+    /// Add or replace a name. Names are case insensitive.
+    pub fn register(&mut self, name: &str, ease: Box<DynEase + 'static>) {
+        self.entries.insert(lower(name), ease);
+    }
+
+    /// Find an easing and the mode it's meant to be used with. Names without
+    /// a mode use `In`.
+    pub fn lookup(&self, name: &str) -> Option<(Box<DynEase + 'static>, Mode)> {
+        let name = lower(name.trim());
+        let name = name.as_slice();
+
+        match self.entries.get(&name.to_string()) {
+            Some(e) => return Some((e.clone(), In)),
+            None => {}
+        }
+
+        match call(name) {
+            Some((f, args)) => return parse_call(f, args.as_slice()).map(|e| (e, In)),
+            None => {}
+        }
+
+        if name.starts_with("ease") {
+            let rest = name.slice_from(4);
+            // longest prefix first, `inout` starts with `in`
+            for &(prefix, mode) in [("inout", InOut), ("outin", OutIn), ("in", In), ("out", Out)].iter() {
+                if rest.starts_with(prefix) {
+                    return self.entries.get(&rest.slice_from(prefix.len()).to_string())
+                        .map(|e| (e.clone(), mode));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Split `"name(a, b)"` into the name and its arguments.
+fn call<'a>(s: &'a str) -> Option<(&'a str, Vec<&'a str>)> {
+    match s.find('(') {
+        Some(i) if s.ends_with(")") => {
+            let args = s.slice(i + 1, s.len() - 1).split(',').map(|a| a.trim()).collect();
+            Some((s.slice_to(i).trim(), args))
+        }
+        _ => None
+    }
+}
+
+/// Parse a parameterized easing. Arguments the easing would reject are
+/// treated as unknown names instead.
+fn parse_call(name: &str, args: &[&str]) -> Option<Box<DynEase + 'static>> {
+    if name == "steps" {
+        return parse_steps(args);
+    }
+    let nums: Vec<Option<f64>> = args.iter().map(|a| number(*a)).collect();
+    let bounces = |n: f64| n >= 1. && n <= MAX_BOUNCES as f64 && n == n.floor();
+    match (name, nums.as_slice()) {
+        // CSS requires the x values in [0;1]
+        ("cubic-bezier", [Some(x1), Some(y1), Some(x2), Some(y2)])
+            if x1 >= 0. && x1 <= 1. && x2 >= 0. && x2 <= 1. =>
+            Some(boxed(ease::bezier(x1, y1, x2, y2))),
+        ("power", [Some(p)]) if p > 0. && p.is_finite() =>
+            Some(boxed(ease::power(p))),
+        ("back", [Some(s)]) if s >= 0. && s.is_finite() =>
+            Some(boxed(ease::back().overshoot(s))),
+        ("elastic", [Some(a)]) if a >= 1. && a.is_finite() =>
+            Some(boxed(ease::elastic().amplitude(a))),
+        ("elastic", [Some(a), Some(p)]) if a >= 1. && a.is_finite() && p > 0. && p.is_finite() =>
+            Some(boxed(ease::elastic().amplitude(a).period(p))),
+        ("bounce", [Some(n)]) if bounces(n) =>
+            Some(boxed(ease::bounce().bounces(n as uint))),
+        ("bounce", [Some(n), Some(r)]) if bounces(n) && r > 0. && r < 1. =>
+            Some(boxed(ease::bounce().bounces(n as uint).restitution(r))),
+        _ => None
+    }
+}
+
+/// Parse a number, also with a leading dot like CSS writes them, e.g.
+/// `.17`.
+fn number(s: &str) -> Option<f64> {
+    if s.starts_with(".") {
+        from_str::<f64>(format!("0{}", s).as_slice())
+    } else if s.starts_with("-.") {
+        from_str::<f64>(format!("-0{}", s.slice_from(1)).as_slice())
+    } else {
+        from_str::<f64>(s)
+    }
+}
+
+/// Parse the arguments of CSS `steps()`.
+fn parse_steps(args: &[&str]) -> Option<Box<DynEase + 'static>> {
+    let n = match from_str::<uint>(args[0]) {
+        Some(n) if n >= 1 => n,
+        _ => return None
+    };
+    match args.slice_from(1) {
+        [] | ["end"] | ["jump-end"] => Some(boxed(ease::steps(n))),
+        ["start"] | ["jump-start"] => Some(boxed(ease::steps(n).start())),
+        _ => None
+    }
+}
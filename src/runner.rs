@@ -0,0 +1,124 @@
+//! Ticking a tween tree on its own task, at a fixed rate.
+//!
+//! The tween tree itself is built on the new task by a `proc`, so only the
+//! accessors it captures have to be `Send`, e.g. those from `sync`.
+
+use std::cell::Cell;
+use std::comm::{Empty, Disconnected};
+
+use Tween;
+use clock::{Clock, SystemClock};
+
+enum Command {
+    Start,
+    Stop,
+    Quit
+}
+
+/// Handle to a task updating a tween. The task starts out stopped, and
+/// quits when the tween is done or the handle is dropped.
+pub struct Runner {
+    commands: Sender<Command>,
+    finished: Receiver<()>,
+    running: Cell<bool>
+}
+
+impl Runner {
+    /// Spawn a task that updates the tween built by `build` `rate` times
+    /// per second, by `1 / rate` each time. `rate` has to be positive.
+    pub fn spawn(rate: f64, build: proc() -> Box<Tween + 'static>) -> Runner {
+        Runner::spawn_with_clock(SystemClock::new(), rate, build)
+    }
+
+    /// Like `spawn`, but waits for the ticks on `clock`. With a
+    /// `ManualClock`, the ticks follow each other without waiting.
+    pub fn spawn_with_clock<C: Clock + Send>(clock: C, rate: f64, build: proc() -> Box<Tween + 'static>)
+    -> Runner {
+        assert!(rate > 0., "the rate of a runner must be positive, got {}", rate);
+        let (commands, cmd_rx) = channel();
+        let (done_tx, finished) = channel();
+
+        spawn(proc() {
+            let mut clock = clock;
+            let mut tween = build();
+            let step = 1. / rate;
+            let mut running = false;
+            // ticks are counted from the last start
+            let mut started = 0.;
+            let mut ticks = 0u64;
+
+            'ticks: loop {
+                loop {
+                    let cmd = if running {
+                        match cmd_rx.try_recv() {
+                            Ok(cmd) => cmd,
+                            Err(Empty) => break,
+                            Err(Disconnected) => Quit
+                        }
+                    } else {
+                        // nothing to do until started again
+                        match cmd_rx.recv_opt() {
+                            Ok(cmd) => cmd,
+                            Err(()) => Quit
+                        }
+                    };
+                    match cmd {
+                        Start => if !running {
+                            running = true;
+                            started = clock.now();
+                            ticks = 0;
+                        },
+                        Stop => running = false,
+                        Quit => break 'ticks
+                    }
+                }
+
+                tween.update(step);
+                if tween.done() {
+                    break;
+                }
+
+                // wait until the next tick is due, so the time spent
+                // updating doesn't add up
+                ticks += 1;
+                clock.wait_until(started + ticks as f64 / rate);
+            }
+
+            let _ = done_tx.send_opt(());
+        });
+
+        Runner {
+            commands: commands,
+            finished: finished,
+            running: Cell::new(false)
+        }
+    }
+
+    /// Start or resume updating.
+    pub fn start(&self) {
+        self.running.set(true);
+        let _ = self.commands.send_opt(Start);
+    }
+
+    /// Pause updating, until `start` is called again.
+    pub fn stop(&self) {
+        self.running.set(false);
+        let _ = self.commands.send_opt(Stop);
+    }
+
+    /// Block until the tween is done, and quit the task. Returns whether
+    /// the tween got done: a runner that isn't started would never finish,
+    /// so for one that is stopped, this returns false right away.
+    pub fn join(self) -> bool {
+        if !self.running.get() {
+            return false;
+        }
+        self.finished.recv_opt().is_ok()
+    }
+}
+
+impl Drop for Runner {
+    fn drop(&mut self) {
+        let _ = self.commands.send_opt(Quit);
+    }
+}
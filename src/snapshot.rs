@@ -0,0 +1,110 @@
+//! Saving the state of a tween tree, to go back to it later, e.g. to load
+//! a saved game in the middle of an animation, or to rewind a few frames
+//! for rollback netcode.
+//!
+//! A `Snapshot` holds the timing state of every node of a tree, like how
+//! far a `Single` is and which child of a `Sequence` is playing, plus the
+//! offsets additive tweens have applied so far. Accessors, easings and
+//! durations are not part of it: a snapshot can only be restored into the
+//! tree it was taken of, or a clone of it. Floats are stored bit for bit,
+//! so playback after restoring is exactly the same as it was after taking
+//! the snapshot.
+//!
+//! ```rust
+//! let snap = tw.snapshot();
+//! tw.update(0.1);
+//! tw.restore(&snap);
+//! ```
+
+use std::mem;
+use std::num::{ToPrimitive, FromPrimitive};
+
+/// The state of a tween tree, see the module documentation. Two snapshots
+/// of the same tree are equal if the tree was in the same state.
+#[deriving(Clone, PartialEq, Eq, Hash, Show)]
+pub struct Snapshot {
+    words: Vec<u64>
+}
+
+impl Snapshot {
+    pub fn new() -> Snapshot {
+        Snapshot {words: Vec::new()}
+    }
+
+    /// The size of the snapshot, in 64 bit words.
+    pub fn len(&self) -> uint {
+        self.words.len()
+    }
+
+    pub fn words(&self) -> &[u64] {
+        self.words.as_slice()
+    }
+
+    #[inline]
+    pub fn push_f64(&mut self, val: f64) {
+        self.words.push(unsafe { mem::transmute::<f64, u64>(val) });
+    }
+
+    #[inline]
+    pub fn push_uint(&mut self, val: uint) {
+        self.words.push(val as u64);
+    }
+
+    #[inline]
+    pub fn push_bool(&mut self, val: bool) {
+        self.words.push(val as u64);
+    }
+
+    /// Push a tweened value. Values are converted to `f64`, which holds
+    /// every primitive float exactly.
+    #[inline]
+    pub fn push_value<T: ToPrimitive>(&mut self, val: T) {
+        self.push_f64(val.to_f64().unwrap());
+    }
+
+    /// Read the words back, in the order they were pushed.
+    pub fn reader<'a>(&'a self) -> Reader<'a> {
+        Reader {words: self.words.as_slice(), pos: 0}
+    }
+}
+
+/// Reads a `Snapshot`. Reading past its end panics, since it means the
+/// snapshot was taken of a different tree.
+pub struct Reader<'a> {
+    words: &'a [u64],
+    pos: uint
+}
+
+impl<'a> Reader<'a> {
+    #[inline]
+    fn next(&mut self) -> u64 {
+        assert!(self.pos < self.words.len(), "snapshot doesn't match the tween tree");
+        self.pos += 1;
+        self.words[self.pos - 1]
+    }
+
+    /// Check if every word has been read.
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.words.len()
+    }
+
+    #[inline]
+    pub fn f64(&mut self) -> f64 {
+        unsafe { mem::transmute::<u64, f64>(self.next()) }
+    }
+
+    #[inline]
+    pub fn uint(&mut self) -> uint {
+        self.next() as uint
+    }
+
+    #[inline]
+    pub fn bool(&mut self) -> bool {
+        self.next() != 0
+    }
+
+    #[inline]
+    pub fn value<T: FromPrimitive>(&mut self) -> T {
+        FromPrimitive::from_f64(self.f64()).unwrap()
+    }
+}
@@ -0,0 +1,149 @@
+//! Accessors that are `Send + Sync`, so a tween can be driven on another
+//! task while the value is read elsewhere. See `runner` for a task that
+//! does the driving.
+
+use std::mem;
+use std::sync::{Arc, Mutex, RWLock};
+use std::sync::atomic::{AtomicUint, SeqCst};
+
+use Access;
+
+/// Lock-free access to an `f64`, stored as its bit pattern.
+/// Only available where a `uint` is wide enough to hold the bits.
+#[cfg(target_word_size = "64")]
+#[deriving(Clone)]
+pub struct AtomicF64 {
+    bits: Arc<AtomicUint>
+}
+
+#[cfg(target_word_size = "64")]
+impl AtomicF64 {
+    pub fn new(val: f64) -> AtomicF64 {
+        AtomicF64 {
+            bits: Arc::new(AtomicUint::new(unsafe { mem::transmute::<f64, u64>(val) } as uint))
+        }
+    }
+
+    #[inline]
+    pub fn load(&self) -> f64 {
+        unsafe { mem::transmute::<u64, f64>(self.bits.load(SeqCst) as u64) }
+    }
+
+    #[inline]
+    pub fn store(&self, val: f64) {
+        self.bits.store(unsafe { mem::transmute::<f64, u64>(val) } as uint, SeqCst);
+    }
+}
+
+#[cfg(target_word_size = "64")]
+impl Access<f64> for AtomicF64 {
+    #[inline]
+    fn get(&self) -> f64 {
+        self.load()
+    }
+
+    #[inline]
+    fn set(&mut self, val: f64) {
+        self.store(val);
+    }
+}
+
+/// Lock-free access to an `f32`, stored as its bit pattern.
+#[deriving(Clone)]
+pub struct AtomicF32 {
+    bits: Arc<AtomicUint>
+}
+
+impl AtomicF32 {
+    pub fn new(val: f32) -> AtomicF32 {
+        AtomicF32 {
+            bits: Arc::new(AtomicUint::new(unsafe { mem::transmute::<f32, u32>(val) } as uint))
+        }
+    }
+
+    #[inline]
+    pub fn load(&self) -> f32 {
+        unsafe { mem::transmute::<u32, f32>(self.bits.load(SeqCst) as u32) }
+    }
+
+    #[inline]
+    pub fn store(&self, val: f32) {
+        self.bits.store(unsafe { mem::transmute::<f32, u32>(val) } as uint, SeqCst);
+    }
+}
+
+impl Access<f32> for AtomicF32 {
+    #[inline]
+    fn get(&self) -> f32 {
+        self.load()
+    }
+
+    #[inline]
+    fn set(&mut self, val: f32) {
+        self.store(val);
+    }
+}
+
+/// Access via a shared mutex. The lock is only held for the single read
+/// or write. If another task panicked while holding the lock, the value
+/// may be half written, so the mutex is poisoned and updating the tween
+/// panics as well, instead of tweening on from a broken value.
+impl<T: Copy + Send> Access<T> for Arc<Mutex<T>> {
+    #[inline]
+    fn get(&self) -> T {
+        *self.lock()
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        *self.lock() = val;
+    }
+}
+
+/// Access via a shared reader-writer lock, for values that are read far
+/// more often than they are tweened. Poisoning is handled like for
+/// `Arc<Mutex<T>>`.
+impl<T: Copy + Send + Sync> Access<T> for Arc<RWLock<T>> {
+    #[inline]
+    fn get(&self) -> T {
+        *self.read()
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        *self.write() = val;
+    }
+}
+
+/// Sends every value written by a tween down a channel, instead of storing
+/// it. Reading returns the last value sent, by this accessor or any of its
+/// clones. Values sent after the receiving end has hung up are dropped
+/// silently.
+#[deriving(Clone)]
+pub struct ChannelAccess<T> {
+    tx: Sender<T>,
+    last: Arc<Mutex<T>>
+}
+
+impl<T: Copy + Send> ChannelAccess<T> {
+    /// Create a sink that starts out at `initial`, without sending it.
+    pub fn new(tx: Sender<T>, initial: T) -> ChannelAccess<T> {
+        ChannelAccess {
+            tx: tx,
+            last: Arc::new(Mutex::new(initial))
+        }
+    }
+}
+
+impl<T: Copy + Send> Access<T> for ChannelAccess<T> {
+    #[inline]
+    fn get(&self) -> T {
+        *self.last.lock()
+    }
+
+    #[inline]
+    fn set(&mut self, val: T) {
+        *self.last.lock() = val;
+        let _ = self.tx.send_opt(val);
+    }
+}
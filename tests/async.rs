@@ -0,0 +1,48 @@
+//! Sampling tweens driven by a clock.
+
+#![cfg(feature = "async")]
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use tween::Tween;
+use tween::async::{samples, Sample, Pending, Ended};
+use tween::clock::ManualClock;
+use tween::ease::{linear, In};
+use tween::props::{MultiProperty, prop};
+
+#[test]
+fn samples_at_interval() {
+    let val = Rc::new(Cell::new(0.));
+    let tw = box MultiProperty::new(vec![prop(val.clone(), 0., 10.)], linear(), In, 1.);
+    let mut clock = ManualClock::new();
+    clock.set(2.);
+    let got: Vec<(f64, f64)> = samples(tw as Box<Tween + 'static>, val.clone(), clock, 0.25).collect();
+    assert_eq!(got, vec![(2.25, 2.5), (2.5, 5.), (2.75, 7.5), (3., 10.)]);
+}
+
+#[test]
+fn poll_without_waiting() {
+    let val = Rc::new(Cell::new(0.));
+    let tw = box MultiProperty::new(vec![prop(val.clone(), 0., 10.)], linear(), In, 1.);
+    let mut s = samples(tw as Box<Tween + 'static>, val.clone(), ManualClock::new(), 0.25);
+    assert_eq!(s.poll(), Pending);
+    s.clock().advance(0.25);
+    assert_eq!(s.poll(), Sample(0.25, 2.5));
+    assert_eq!(s.poll(), Pending);
+    // the sample at 0.5 was missed
+    s.clock().advance(0.5);
+    assert_eq!(s.poll(), Sample(0.75, 7.5));
+    assert_eq!(s.poll(), Pending);
+    s.clock().advance(0.25);
+    assert_eq!(s.poll(), Sample(1., 10.));
+    assert_eq!(s.poll(), Ended);
+}
+
+#[test]
+#[should_fail]
+fn zero_interval() {
+    samples(tween::pause(1.), Rc::new(Cell::new(0.)), ManualClock::new(), 0.);
+}
@@ -0,0 +1,78 @@
+//! Updating many scalar tweens at once.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::mem;
+use std::rc::Rc;
+
+use tween::{Tween, from_to};
+use tween::batch::{Batch, EaseKind, Linear, Quad, Cubic, Quart, Quint, Sine, Circ, Back, Elastic, Bounce};
+use tween::ease;
+use tween::ease::{Ease, Mode, In, Out, InOut, OutIn};
+
+fn bits(v: f64) -> u64 {
+    unsafe { mem::transmute::<f64, u64>(v) }
+}
+
+/// Play a `Single` from 3 to -7 over 1.5 a bit past its end, recording the
+/// bits of the value every 1/60th.
+fn single<E: Ease + 'static>(ease: E, mode: Mode) -> Vec<u64> {
+    let val = Rc::new(Cell::new(0.));
+    let mut tw = from_to(val.clone(), 3., -7., ease, mode, 1.5);
+    range(0u, 100).map(|_| { tw.update(1. / 60.); bits(val.get()) }).collect()
+}
+
+/// The same with a tween in a `Batch`.
+fn batch(kind: EaseKind, mode: Mode) -> Vec<u64> {
+    let mut b = Batch::new();
+    b.add(3., -7., 1.5, kind, mode);
+    let mut out = [0.];
+    range(0u, 100).map(|_| { b.update(1. / 60., &mut out); bits(out[0]) }).collect()
+}
+
+#[test]
+fn same_as_single() {
+    for &mode in [In, Out, InOut, OutIn].iter() {
+        let pairs = vec![
+            (Linear, single(ease::linear(), mode)),
+            (Quad, single(ease::quad(), mode)),
+            (Cubic, single(ease::cubic(), mode)),
+            (Quart, single(ease::quart(), mode)),
+            (Quint, single(ease::quint(), mode)),
+            (Sine, single(ease::sine(), mode)),
+            (Circ, single(ease::circ(), mode)),
+            (Back, single(ease::back(), mode)),
+            (Elastic, single(ease::elastic(), mode)),
+            (Bounce, single(ease::bounce(), mode))
+        ];
+        for (kind, expected) in pairs.into_iter() {
+            assert!(batch(kind.clone(), mode) == expected, "{} {} differs from a Single", kind, mode);
+        }
+    }
+}
+
+#[test]
+fn zero_duration() {
+    let mut b = Batch::new();
+    b.add(0., 10., 0., Quad, In);
+    b.add(0., 10., 2., Linear, In);
+    assert!(b.done(0));
+
+    let mut out = [0., 0.];
+    b.update(1., &mut out);
+    assert_eq!(out[0], 10.);
+    assert_eq!(out[1], 5.);
+}
+
+#[test]
+fn not_before_the_start() {
+    let mut b = Batch::new();
+    b.add(0., 10., 2., Linear, In);
+    let mut out = [0.];
+    b.update(1., &mut out);
+    b.update(-3., &mut out);
+    assert_eq!(out[0], 0.);
+    b.update(0.5, &mut out);
+    assert_eq!(out[0], 2.5);
+}
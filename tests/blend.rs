@@ -0,0 +1,127 @@
+//! Crossfading between tweens, and clones of blends.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::f64::NAN;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+
+use tween::{Tween, from_to, seq, pause, exec};
+use tween::ease::{Mode, linear, quad, In, Out};
+use tween::blend::{Blend, Slot, crossfade};
+
+fn moving(slots: &[Slot<f64>], start: f64, end: f64) -> Box<Tween + 'static> {
+    box from_to(slots[0].clone(), start, end, linear(), In, 1.) as Box<Tween + 'static>
+}
+
+fn fade(target: Rc<Cell<f64>>, mode: Mode) -> Blend<f64, Rc<Cell<f64>>> {
+    crossfade(vec![target], |s| moving(s, 0., 10.), |s| moving(s, 20., 40.), 1., linear(), mode)
+}
+
+#[test]
+fn crossfade_completes() {
+    let target = Rc::new(Cell::new(0.));
+    let mut b = fade(target.clone(), In);
+    b.update(0.5);
+    assert_eq!(b.weights(), vec![0.5, 0.5]);
+    assert_eq!(target.get(), 0.5 * 5. + 0.5 * 30.);
+    b.update(0.5);
+    assert!(b.done());
+    assert_eq!(b.weights(), vec![0., 1.]);
+    assert_eq!(target.get(), 40.);
+}
+
+#[test]
+fn crossfade_mode() {
+    let target = Rc::new(Cell::new(0.));
+    let mut b = crossfade(vec![target.clone()], |s| moving(s, 0., 10.), |s| moving(s, 20., 40.),
+                          1., quad(), Out);
+    b.update(0.5);
+    assert_eq!(b.weights(), vec![0.25, 0.75]);
+    assert_eq!(target.get(), 0.25 * 5. + 0.75 * 30.);
+}
+
+#[test]
+fn crossfade_without_duration() {
+    let target = Rc::new(Cell::new(0.));
+    let mut b = crossfade(vec![target.clone()], |s| moving(s, 0., 10.), |s| moving(s, 20., 40.),
+                          0., linear(), In);
+    b.update(0.);
+    assert_eq!(b.weights(), vec![0., 1.]);
+    assert_eq!(target.get(), 20.);
+    b.update(0.5);
+    assert_eq!(target.get(), 30.);
+}
+
+#[test]
+fn nan_weight_leaves_targets_alone() {
+    let target = Rc::new(Cell::new(5.));
+    let mut b = Blend::new(vec![target.clone()]);
+    b.add_layer(1., |s| moving(s, 0., 10.));
+    b.add_layer(NAN, |s| moving(s, 20., 40.));
+    b.update(0.5);
+    assert_eq!(target.get(), 5.);
+}
+
+static CALLS: AtomicUint = INIT_ATOMIC_UINT;
+
+fn count() {
+    CALLS.fetch_add(1, SeqCst);
+}
+
+#[test]
+fn finished_layers_are_left_alone() {
+    let target = Rc::new(Cell::new(0.));
+    let mut b = Blend::new(vec![target.clone()]);
+    b.add_layer(1., |s| moving(s, 0., 10.));
+    b.add_layer(1., |_| exec(count));
+    for _ in range(0u, 4) {
+        b.update(0.25);
+    }
+    assert_eq!(CALLS.load(SeqCst), 1);
+    assert!(b.done());
+}
+
+#[test]
+fn clones_keep_their_own_weights() {
+    let target = Rc::new(Cell::new(0.));
+    let mut b = fade(target.clone(), In);
+    let mut c = b.clone();
+    b.update(1.);
+    assert_eq!(c.weights(), vec![1., 0.]);
+    c.update(0.25);
+    assert_eq!(c.weights(), vec![0.75, 0.25]);
+    assert_eq!(b.weights(), vec![0., 1.]);
+}
+
+#[test]
+fn reset_restores_weights() {
+    let mut b = fade(Rc::new(Cell::new(0.)), In);
+    b.update(1.);
+    b.reset();
+    assert_eq!(b.weights(), vec![1., 0.]);
+    b.set_weight(1, 3.);
+    assert_eq!(b.weights(), vec![1., 3.]);
+}
+
+#[test]
+fn reset_restores_values() {
+    let target = Rc::new(Cell::new(5.));
+    let mut b = Blend::new(vec![target.clone()]);
+    b.add_layer(1., |s| seq(vec![pause(0.5), moving(s, 10., 20.)]));
+    b.update(1.5);
+    assert_eq!(target.get(), 20.);
+    b.reset();
+    // the layer writes nothing during the pause, so its value is the one
+    // the target had when it was added
+    b.update(0.25);
+    assert_eq!(target.get(), 5.);
+}
+
+#[test]
+#[should_fail]
+fn set_weight_out_of_range() {
+    let mut b = fade(Rc::new(Cell::new(0.)), In);
+    b.set_weight(2, 1.);
+}
@@ -0,0 +1,89 @@
+//! Building tweens with `builder::on`.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+
+use tween::Tween;
+use tween::builder::on;
+use tween::ease::{quad, In};
+
+static CALLS: AtomicUint = INIT_ATOMIC_UINT;
+
+fn count() {
+    CALLS.fetch_add(1, SeqCst);
+}
+
+#[test]
+fn values() {
+    let val = Rc::new(Cell::new(7.));
+    let mut tw = on(val.clone()).from(0.).to(100.).over(1.)
+        .ease(quad()).mode(In)
+        .delay(0.5).repeat(2).yoyo()
+        .build();
+    assert_eq!(tw.remaining(), 3.5);
+
+    // nothing is written during the delay
+    tw.update(0.25);
+    assert_eq!(val.get(), 7.);
+    tw.update(0.75);
+    assert_eq!(val.get(), 25.);
+    tw.update(0.5);
+    assert_eq!(val.get(), 100.);
+
+    // back again with the easing mirrored, so it is quick at first
+    tw.update(0.5);
+    assert_eq!(val.get(), 25.);
+    tw.update(0.25);
+    assert_eq!(val.get(), 6.25);
+
+    // and there once more
+    tw.update(0.5);
+    assert_eq!(val.get(), 6.25);
+    tw.update(0.25);
+    assert_eq!(val.get(), 25.);
+    tw.update(0.5);
+    assert_eq!(val.get(), 100.);
+    assert!(tw.done());
+}
+
+#[test]
+fn on_complete_fires_once() {
+    let val = Rc::new(Cell::new(0.));
+    let mut tw = on(val.clone()).from(0.).to(1.).over(1.).delay(0.5).repeat(2)
+        .on_complete(count).build();
+    tw.update(3.);
+    assert_eq!(CALLS.load(SeqCst), 0);
+    tw.update(0.5);
+    assert_eq!(CALLS.load(SeqCst), 1);
+    tw.update(1.);
+    tw.update(0.);
+    assert_eq!(CALLS.load(SeqCst), 1);
+}
+
+#[test]
+fn repeat_counts() {
+    let val = Rc::new(Cell::new(0.));
+    let mut tw = on(val.clone()).from(0.).to(1.).over(1.).delay(0.5).repeat(2).build();
+    assert_eq!(tw.remaining(), 3.5);
+    tw.update(3.5);
+    assert!(tw.done());
+    assert_eq!(tw.describe().children[1].repeats, Some(3));
+}
+
+#[test]
+fn yoyo_repeats_there_and_back() {
+    let val = Rc::new(Cell::new(0.));
+    let tw = on(val.clone()).from(0.).to(1.).over(1.).repeat(1).yoyo().build();
+    assert_eq!(tw.remaining(), 2.);
+    assert_eq!(tw.describe().kind, "Repeat");
+
+    // an odd number of plays ends with one more there
+    let tw = on(val.clone()).from(0.).to(1.).over(1.).repeat(2).yoyo().build();
+    assert_eq!(tw.remaining(), 3.);
+    let node = tw.describe();
+    assert_eq!(node.children[0].kind, "Repeat");
+    assert_eq!(node.children[1].kind, "Single");
+}
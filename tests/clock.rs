@@ -0,0 +1,123 @@
+//! Driving tweens in fixed steps, and scaled clocks.
+
+extern crate tween;
+
+use std::time::Duration;
+
+use tween::clock::{Clock, Driver, ManualClock, ScaledClock, TickClock};
+
+fn driver() -> Driver<ManualClock> {
+    Driver::new(ManualClock::new(), Duration::milliseconds(100))
+}
+
+#[test]
+fn driver_steps() {
+    let mut driver = driver();
+    driver.clock().advance(0.25);
+    let mut total = 0.;
+    assert_eq!(driver.tick(|d| total += d), 2);
+    assert!((total - 0.2).abs() < 1e-9);
+    assert!((driver.alpha() - 0.5).abs() < 1e-9);
+}
+
+#[test]
+fn driver_whole_steps() {
+    let mut driver = driver();
+    driver.clock().advance(0.3);
+    assert_eq!(driver.tick(|_| {}), 3);
+    assert_eq!(driver.alpha(), 0.);
+}
+
+#[test]
+fn driver_alpha() {
+    let mut driver = driver();
+    driver.clock().advance(0.075);
+    assert_eq!(driver.tick(|_| {}), 0);
+    assert_eq!(driver.alpha(), 0.75);
+    driver.clock().advance(0.05);
+    assert_eq!(driver.tick(|_| {}), 1);
+    assert_eq!(driver.alpha(), 0.25);
+}
+
+#[test]
+fn driver_max_delta() {
+    // a quarter second by default
+    let mut default = driver();
+    default.clock().advance(1.);
+    assert_eq!(default.tick(|_| {}), 2);
+    assert_eq!(default.alpha(), 0.5);
+
+    let mut longer = driver();
+    longer.set_max_delta(Duration::milliseconds(500));
+    longer.clock().advance(1.);
+    assert_eq!(longer.tick(|_| {}), 5);
+    assert_eq!(longer.alpha(), 0.);
+}
+
+#[test]
+#[should_fail]
+fn driver_rejects_zero_step() {
+    Driver::new(ManualClock::new(), Duration::zero());
+}
+
+#[test]
+#[should_fail]
+fn driver_rejects_negative_step() {
+    Driver::new(ManualClock::new(), Duration::milliseconds(-10));
+}
+
+#[test]
+#[should_fail]
+fn driver_rejects_zero_max_delta() {
+    driver().set_max_delta(Duration::zero());
+}
+
+#[test]
+#[should_fail]
+fn driver_rejects_negative_max_delta() {
+    driver().set_max_delta(Duration::milliseconds(-10));
+}
+
+#[test]
+fn scaled_clock() {
+    let mut clock = ScaledClock::new(ManualClock::new(), 2.);
+    clock.inner().advance(1.);
+    assert_eq!(clock.now(), 2.);
+
+    // changing the scale doesn't make the time jump
+    clock.set_scale(0.5);
+    assert_eq!(clock.now(), 2.);
+    clock.inner().advance(2.);
+    assert_eq!(clock.now(), 3.);
+
+    clock.wait_until(4.);
+    assert_eq!(clock.now(), 4.);
+    assert_eq!(clock.inner().now(), 5.);
+}
+
+#[test]
+fn paused_scaled_clock() {
+    let mut clock = ScaledClock::new(ManualClock::new(), 1.);
+    clock.inner().advance(1.);
+    clock.set_scale(0.);
+    clock.inner().advance(1.);
+    assert_eq!(clock.now(), 1.);
+    // returns right away instead of waiting forever
+    clock.wait_until(2.);
+    assert_eq!(clock.now(), 1.);
+}
+
+#[test]
+fn tick_clock() {
+    let mut clock = TickClock::new(4);
+    assert_eq!(clock.advance(3), 0.75);
+    clock.wait_until(1.1);
+    assert_eq!(clock.ticks(), 5);
+    assert_eq!(clock.now(), 1.25);
+}
+
+#[test]
+#[should_fail]
+fn tick_clock_rejects_zero_rate() {
+    TickClock::new(0);
+}
@@ -0,0 +1,89 @@
+//! Flings, snapping to points and bouncing off bounds.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use tween::Tween;
+use tween::decay::{Decay, decay};
+
+/// Play `tw` to the end frame by frame, returning the lowest and highest
+/// value on the way and the largest change in one frame.
+fn play(mut tw: Decay<f64, Rc<Cell<f64>>>, val: &Rc<Cell<f64>>) -> (f64, f64, f64) {
+    let (mut lo, mut hi, mut step) = (val.get(), val.get(), 0f64);
+    while !tw.done() {
+        let before = val.get();
+        tw.update(1. / 60.);
+        lo = lo.min(val.get());
+        hi = hi.max(val.get());
+        step = step.max((val.get() - before).abs());
+    }
+    (lo, hi, step)
+}
+
+#[test]
+fn snap_arrives_smoothly() {
+    let val = Rc::new(Cell::new(0.));
+    // would stop at 75, so it snaps to 60
+    let mut tw = decay(val.clone(), 300., 4.).snap(vec![0., 60., 100.]);
+    let mut before = 0.;
+    while !tw.done() {
+        before = val.get();
+        tw.update(1. / 60.);
+    }
+    assert_eq!(val.get(), 60.);
+    assert!((val.get() - before).abs() < 0.05, "jumped from {} to the snap point", before);
+}
+
+#[test]
+fn slow_snap_takes_time() {
+    let val = Rc::new(Cell::new(0.1));
+    let mut tw = decay(val.clone(), 0., 4.).snap(vec![0., 10.]);
+    assert!(tw.remaining() > 0.);
+    tw.update(1. / 60.);
+    assert!(val.get() > 0. && val.get() < 0.1, "moved to {} in the first frame", val.get());
+    let rest = tw.remaining();
+    tw.update(rest);
+    assert_eq!(val.get(), 0.);
+}
+
+#[test]
+fn settles_on_upper_bound() {
+    let val = Rc::new(Cell::new(0.));
+    // would stop at 75
+    let (_, hi, step) = play(decay(val.clone(), 300., 4.).bounds(0., 50., 100.), &val);
+    assert!(hi > 50., "didn't overscroll");
+    assert!(step < 5.1, "jumped by {} in a frame", step);
+    assert_eq!(val.get(), 50.);
+}
+
+#[test]
+fn settles_on_lower_bound() {
+    let val = Rc::new(Cell::new(0.));
+    // would stop at -75
+    let (lo, _, step) = play(decay(val.clone(), -300., 4.).bounds(-20., 100., 100.), &val);
+    assert!(lo < -20., "didn't overscroll");
+    assert!(step < 5.1, "jumped by {} in a frame", step);
+    assert_eq!(val.get(), -20.);
+}
+
+#[test]
+fn released_past_bound() {
+    let val = Rc::new(Cell::new(120.));
+    let (lo, _, _) = play(decay(val.clone(), 0., 4.).bounds(0., 100., 20.), &val);
+    assert!(lo >= 100. - 0.01, "overshot to {}", lo);
+    assert_eq!(val.get(), 100.);
+}
+
+#[test]
+#[should_fail]
+fn zero_friction() {
+    decay(Rc::new(Cell::new(0.)), 300., 0.);
+}
+
+#[test]
+#[should_fail]
+fn negative_stiffness() {
+    decay(Rc::new(Cell::new(0.)), 300., 4.).bounds(0., 100., -1.);
+}
@@ -0,0 +1,74 @@
+//! Rendering tween trees as text and Graphviz graphs.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use tween::{Tween, from_to, seq, par, rep, pause};
+use tween::ease::{linear, quint, In, InOut};
+use tween::describe::{text, dot};
+use tween::dsl::{label, times};
+
+/// A repeated `Parallel` of a `Sequence` and a `Single`, half a second
+/// into its second cycle, where the `Single` just finished.
+fn tree() -> Box<Tween + 'static> {
+    let (x, y) = (Rc::new(Cell::new(0.)), Rc::new(Cell::new(0.)));
+    let mut tw = rep(par(vec![
+        seq(vec![
+            pause(1.),
+            box from_to(x, 0., 1., quint(), InOut, 1.) as Box<Tween + 'static>
+        ]),
+        box from_to(y, 0., 1., linear(), In, 0.5) as Box<Tween + 'static>
+    ]));
+    tw.update(2.5);
+    tw
+}
+
+#[test]
+fn text_() {
+    assert_eq!(text(&tree().describe()).as_slice(), "\
+Repeat  elapsed 2.5  remaining inf  repeats 1
+  Parallel  elapsed 0.5  remaining 1.5
+    Sequence  elapsed 0.5  remaining 1.5  current 0
+      Pause  elapsed 0.5  remaining 0.5
+      Single quint InOut  elapsed 0  remaining 1
+    Single linear In  elapsed 0.5  remaining 0
+");
+}
+
+#[test]
+fn dot_() {
+    assert_eq!(dot(&tree().describe()).as_slice(), r#"digraph tween {
+    node [shape=box, fontname=monospace];
+    n0 [label="Repeat\nelapsed 2.5\nremaining inf\nrepeats 1"];
+    n1 [label="Parallel\nelapsed 0.5\nremaining 1.5"];
+    n0 -> n1;
+    n2 [label="Sequence\nelapsed 0.5\nremaining 1.5\ncurrent 0"];
+    n1 -> n2;
+    n3 [label="Pause\nelapsed 0.5\nremaining 0.5"];
+    n2 -> n3 [style=bold];
+    n4 [label="Single quint InOut\nelapsed 0\nremaining 1"];
+    n2 -> n4;
+    n5 [label="Single linear In\nelapsed 0.5\nremaining 0", style=filled, fillcolor=gray90];
+    n1 -> n5;
+}
+"#);
+}
+
+#[test]
+fn repeat_counts_every_cycle() {
+    let mut tw = times(3, pause(1.));
+    tw.update(2.25);
+    let node = tw.describe();
+    assert_eq!(node.elapsed, 2.25);
+    assert_eq!(node.children[0].elapsed, 0.25);
+    // the whole play, as plots lay it out
+    assert_eq!(node.elapsed + node.remaining, 3.);
+}
+
+#[test]
+fn dot_escapes_labels() {
+    let node = label("C:\\ \"tmp\"", pause(1.)).describe();
+    assert!(dot(&node).as_slice().contains(r#"n0 [label="Pause \"C:\\ \"tmp\"\"\nelapsed 0\nremaining 1"];"#));
+}
@@ -0,0 +1,136 @@
+//! Checks that tweens produce exactly the values in `tests/golden` with the
+//! `deterministic` feature, on every platform.
+//!
+//! Every golden file holds the bits of the tweened value after each tick,
+//! one per line in hex. After an intended change, rewrite them by running
+//! the tests with `TWEEN_BLESS` set:
+//!
+//! ```sh
+//! TWEEN_BLESS=1 cargo test --features deterministic --test deterministic
+//! ```
+//!
+//! The files checked in were not written that way, but computed by a script
+//! repeating the same double precision operations as `ease` and the tweens
+//! here, step by step. Bless them on the next build that runs this suite
+//! and check that the diff is empty.
+
+#![cfg(feature = "deterministic")]
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::io::File;
+use std::mem;
+use std::num;
+use std::os;
+use std::rc::Rc;
+
+use tween::{Tween, from_to};
+use tween::ease::{Ease, Mode, In, Out, InOut};
+use tween::ease::{linear, quad, quint, power, sine, circ, back, bounce, elastic};
+use tween::clock::TickClock;
+use tween::decay::decay;
+
+static RATE: u32 = 60;
+
+fn golden(name: &str) -> Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join_many(["tests", "golden", format!("{}.txt", name).as_slice()])
+}
+
+/// Update `tw` tick by tick, recording the bits of the value after each.
+fn record<T: Tween>(mut tw: T, val: Rc<Cell<f64>>, ticks: uint) -> Vec<u64> {
+    let mut clock = TickClock::new(RATE);
+    let mut bits = Vec::with_capacity(ticks);
+    for _ in range(0, ticks) {
+        tw.update(clock.advance(1));
+        bits.push(unsafe { mem::transmute::<f64, u64>(val.get()) });
+    }
+    bits
+}
+
+fn check(name: &str, bits: Vec<u64>) {
+    let path = golden(name);
+    if os::getenv("TWEEN_BLESS").is_some() {
+        let text: Vec<String> = bits.iter().map(|b| format!("{:016x}\n", b)).collect();
+        File::create(&path).write_str(text.concat().as_slice()).unwrap();
+        return;
+    }
+
+    let text = File::open(&path).read_to_string().unwrap();
+    let expected: Vec<u64> = text.as_slice().lines()
+        .map(|l| num::from_str_radix(l, 16).unwrap())
+        .collect();
+    assert_eq!(bits.len(), expected.len());
+    for (tick, (&b, &e)) in bits.iter().zip(expected.iter()).enumerate() {
+        assert!(b == e, "{}: tick {} is {:016x}, expected {:016x}", name, tick + 1, b, e);
+    }
+}
+
+/// One second from 0 to 100, recorded a bit past the end.
+fn eased<E: Ease + 'static>(name: &str, ease: E, mode: Mode) {
+    let val = Rc::new(Cell::new(0f64));
+    let tw = from_to(val.clone(), 0., 100., ease, mode, 1.);
+    check(name, record(tw, val, 70));
+}
+
+#[test]
+fn linear_in() {
+    eased("linear_in", linear(), In);
+}
+
+#[test]
+fn quad_in_out() {
+    eased("quad_in_out", quad(), InOut);
+}
+
+#[test]
+fn quint_out() {
+    eased("quint_out", quint(), Out);
+}
+
+#[test]
+fn power_in() {
+    eased("power_in", power(2.5), In);
+}
+
+#[test]
+fn sine_out() {
+    eased("sine_out", sine(), Out);
+}
+
+#[test]
+fn sine_in_out() {
+    eased("sine_in_out", sine(), InOut);
+}
+
+#[test]
+fn circ_out() {
+    eased("circ_out", circ(), Out);
+}
+
+#[test]
+fn back_in_out() {
+    eased("back_in_out", back(), InOut);
+}
+
+#[test]
+fn bounce_out() {
+    eased("bounce_out", bounce(), Out);
+}
+
+#[test]
+fn elastic_out() {
+    eased("elastic_out", elastic(), Out);
+}
+
+#[test]
+fn elastic_in_out() {
+    eased("elastic_in_out", elastic(), InOut);
+}
+
+#[test]
+fn decay_fling() {
+    let val = Rc::new(Cell::new(0f64));
+    let tw = decay(val.clone(), 300., 4.);
+    check("decay", record(tw, val, 110));
+}
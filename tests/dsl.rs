@@ -0,0 +1,87 @@
+//! Building tween trees with the `tween!` macro.
+
+#![feature(phase)]
+
+#[phase(plugin, link)]
+extern crate tween;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+
+use tween::{Tween, from_to, par};
+use tween::ease::{linear, In};
+
+#[test]
+fn times_and_forever() {
+    let tw = tween!(seq[
+        times(3, pause(0.5)),
+        label("rest", pause(1.))
+    ]);
+    assert_eq!(tw.remaining(), 2.5);
+    let node = tw.describe();
+    assert_eq!(node.children[0].kind, "Repeat");
+    assert_eq!(node.children[1].label, Some("rest"));
+
+    let tw = tween!(forever(pause(1.)));
+    assert!(!tw.remaining().is_finite());
+}
+
+#[test]
+fn tw_boxes() {
+    let x = Rc::new(Cell::new(0.));
+    let mut tw = tween!(par[
+        tw(from_to(x.clone(), 0., 1., linear(), In, 2.)),
+        pause(1.)
+    ]);
+    assert_eq!(tw.remaining(), 2.);
+    tw.update(2.);
+    assert!(tw.done());
+}
+
+static CALLS: AtomicUint = INIT_ATOMIC_UINT;
+
+fn count() {
+    CALLS.fetch_add(1, SeqCst);
+}
+
+#[test]
+fn call() {
+    let mut tw = tween!(seq[
+        pause(0.5),
+        call(count),
+        pause(0.5),
+        call(count)
+    ]);
+    assert_eq!(tw.describe().children[1].kind, "Exec");
+    tw.update(0.25);
+    assert_eq!(CALLS.load(SeqCst), 0);
+    tw.update(0.5);
+    assert_eq!(CALLS.load(SeqCst), 1);
+    tw.update(1.);
+    assert_eq!(CALLS.load(SeqCst), 2);
+    assert!(tw.done());
+
+    let mut tw = tween!(call(count));
+    assert_eq!(tw.update(0.5), 0.5);
+    assert_eq!(CALLS.load(SeqCst), 3);
+}
+
+#[test]
+fn par_of_one() {
+    let x = Rc::new(Cell::new(0.));
+    let mut tw = tween!(par[tw(from_to(x.clone(), 0., 1., linear(), In, 1.))]);
+    let node = tw.describe();
+    assert_eq!(node.kind, "Parallel");
+    assert_eq!(node.children.len(), 1);
+    assert_eq!(tw.remaining(), 1.);
+    tw.update(0.5);
+    assert_eq!(x.get(), 0.5);
+    tw.update(0.5);
+    assert!(tw.done());
+}
+
+#[test]
+fn empty_parallel() {
+    assert_eq!(par(vec![]).remaining(), 0.);
+}
@@ -0,0 +1,220 @@
+//! Easing combinators and modes.
+
+extern crate tween;
+
+use std::f64::NAN;
+use std::f64::consts::PI;
+
+use tween::ease::{Ease, Mode, In, Out, InOut, OutIn, linear, quad, power, back, elastic, bounce};
+use tween::ease::{reverse, mirror, chain, mix, gamma, clamp};
+
+#[test]
+fn reverse_() {
+    let e = reverse(quad());
+    assert_eq!(e.ease_in(0.), 1.);
+    assert_eq!(e.ease_in(0.25), 0.5625);
+    assert_eq!(e.ease_in(1.), 0.);
+}
+
+#[test]
+fn mirror_() {
+    let e = mirror(quad());
+    assert_eq!(e.ease_in(0.), 0.);
+    assert_eq!(e.ease_in(0.25), 0.25);
+    assert_eq!(e.ease_in(0.5), 1.);
+    assert_eq!(e.ease_in(0.75), 0.25);
+    assert_eq!(e.ease_in(1.), 0.);
+}
+
+#[test]
+fn chain_() {
+    let e = chain(quad(), linear(), 0.5);
+    assert_eq!(e.ease_in(0.), 0.);
+    assert_eq!(e.ease_in(0.25), 0.125);
+    assert_eq!(e.ease_in(0.5), 0.5);
+    assert_eq!(e.ease_in(0.75), 0.75);
+    assert_eq!(e.ease_in(1.), 1.);
+}
+
+#[test]
+fn mix_() {
+    let e = mix(linear(), quad(), 0.25);
+    assert_eq!(e.ease_in(0.), 0.);
+    assert_eq!(e.ease_in(0.5), 0.75 * 0.5 + 0.25 * 0.25);
+    assert_eq!(e.ease_in(1.), 1.);
+}
+
+#[test]
+fn gamma_() {
+    let e = gamma(linear(), 2.);
+    assert_eq!(e.ease_in(0.), 0.);
+    assert!((e.ease_in(0.5) - 0.25).abs() < 1e-12);
+    assert_eq!(e.ease_in(1.), 1.);
+}
+
+#[test]
+fn clamp_() {
+    let e = clamp(back());
+    assert!(back().ease_in(0.2) < 0.);
+    assert_eq!(e.ease_in(0.2), 0.);
+    assert_eq!(e.velocity_in(0.2), 0.);
+    assert_eq!(e.ease_in(0.9), back().ease_in(0.9));
+    assert_eq!(e.ease_in(0.), 0.);
+    assert_eq!(e.ease_in(1.), 1.);
+}
+
+#[test]
+fn out_in() {
+    let e = quad();
+    assert_eq!(e.ease(OutIn, 0.), 0.);
+    assert_eq!(e.ease(OutIn, 0.25), 0.375);
+    assert_eq!(e.ease(OutIn, 0.5), 0.5);
+    assert_eq!(e.ease(OutIn, 0.75), 0.625);
+    assert_eq!(e.ease(OutIn, 1.), 1.);
+}
+
+#[test]
+#[should_fail]
+fn mix_out_of_range() {
+    mix(linear(), quad(), 1.5);
+}
+
+#[test]
+#[should_fail]
+fn mix_nan() {
+    mix(linear(), quad(), NAN);
+}
+
+#[test]
+#[should_fail]
+fn gamma_zero() {
+    gamma(linear(), 0.);
+}
+
+#[test]
+#[should_fail]
+fn gamma_nan() {
+    gamma(linear(), NAN);
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+/// Compare an easing with a reference in every mode but `OutIn`, at 101
+/// points.
+fn matches<E: Ease>(e: E, mode: Mode, reference: |f64| -> f64) {
+    for i in range(0u, 101) {
+        let t = i as f64 / 100.;
+        let (a, b) = (e.ease(mode, t), reference(t));
+        assert!(close(a, b), "{} {} at {}: {} instead of {}", e.name(), mode, t, a, b);
+    }
+}
+
+/// Penner's bounce, as the crate had it before bounces were parameterized.
+fn penner_bounce(t: f64) -> f64 {
+    if t < 1. / 2.75 {
+        7.5625 * t * t
+    } else if t < 2. / 2.75 {
+        let s = t - 1.5 / 2.75;
+        7.5625 * s * s + 0.75
+    } else if t < 2.5 / 2.75 {
+        let s = t - 2.25 / 2.75;
+        7.5625 * s * s + 0.9375
+    } else {
+        let s = t - 2.625 / 2.75;
+        7.5625 * s * s + 0.984375
+    }
+}
+
+/// Penner's elastic out with an amplitude of 1 and period `p`.
+fn penner_elastic(t: f64, p: f64) -> f64 {
+    if t == 0. || t == 1. {
+        return t;
+    }
+    2f64.powf(-10. * t) * ((t - p / 4.) * (2. * PI) / p).sin() + 1.
+}
+
+/// Penner's back out with overshoot `s`.
+fn penner_back(t: f64, s: f64) -> f64 {
+    let u = t - 1.;
+    u * u * ((s + 1.) * u + s) + 1.
+}
+
+#[test]
+fn bounce_defaults() {
+    matches(bounce(), Out, |t| penner_bounce(t));
+    matches(bounce(), In, |t| 1. - penner_bounce(1. - t));
+    matches(bounce().bounces(4).restitution(0.5), Out, |t| penner_bounce(t));
+}
+
+#[test]
+fn elastic_defaults() {
+    matches(elastic(), Out, |t| penner_elastic(t, 0.3));
+    matches(elastic(), In, |t| 1. - penner_elastic(1. - t, 0.3));
+    matches(elastic(), InOut, |t| {
+        if t < 0.5 {
+            0.5 - 0.5 * penner_elastic(1. - 2. * t, 0.45)
+        } else {
+            0.5 + 0.5 * penner_elastic(2. * t - 1., 0.45)
+        }
+    });
+    matches(elastic().amplitude(1.).period(0.3), Out, |t| penner_elastic(t, 0.3));
+}
+
+#[test]
+fn back_defaults() {
+    matches(back(), Out, |t| penner_back(t, 1.70158));
+    matches(back(), In, |t| 1. - penner_back(1. - t, 1.70158));
+    matches(back().overshoot(1.70158), Out, |t| penner_back(t, 1.70158));
+    matches(back().overshoot(0.), Out, |t| 1. - (1. - t) * (1. - t) * (1. - t));
+}
+
+#[test]
+fn power_() {
+    matches(power(2.), In, |t| t * t);
+    matches(power(2.5), In, |t| t.powf(2.5));
+    matches(power(0.5), Out, |t| 1. - (1. - t).sqrt());
+}
+
+#[test]
+#[should_fail]
+fn small_amplitude() {
+    elastic().amplitude(0.5);
+}
+
+#[test]
+#[should_fail]
+fn zero_period() {
+    elastic().period(0.);
+}
+
+#[test]
+#[should_fail]
+fn negative_overshoot() {
+    back().overshoot(-1.);
+}
+
+#[test]
+#[should_fail]
+fn no_bounces() {
+    bounce().bounces(0);
+}
+
+#[test]
+#[should_fail]
+fn full_restitution() {
+    bounce().restitution(1.);
+}
+
+#[test]
+#[should_fail]
+fn zero_power() {
+    power(0.);
+}
+
+#[test]
+#[should_fail]
+fn nan_power() {
+    power(NAN);
+}
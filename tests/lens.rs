@@ -0,0 +1,135 @@
+//! Tweening fields of structs through lenses, also of values in an
+//! `Arena` that are removed while the tween runs.
+
+#![feature(phase)]
+
+#[phase(plugin, link)]
+extern crate tween;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use tween::{Access, Tween, from_to};
+use tween::ease::{linear, In};
+use tween::lens::{Arena, Lens};
+
+struct Pos {
+    x: f64,
+    y: f64
+}
+
+struct Player {
+    pos: Pos,
+    alpha: f32
+}
+
+lenses! {
+    Player {
+        x: pos.x -> f64,
+        alpha: alpha -> f32
+    }
+}
+
+fn player() -> Player {
+    Player {pos: Pos {x: 0., y: 5.}, alpha: 1.}
+}
+
+/// Tween `player.pos.x` from 0 to 10 through `acc`, checking it halfway
+/// and at the end with `x`.
+fn moves<A: Access<f64> + 'static>(acc: A, x: || -> f64) {
+    let mut tw = from_to(acc, 0., 10., linear(), In, 1.);
+    tw.update(0.5);
+    assert_eq!(x(), 5.);
+    tw.update(0.5);
+    assert_eq!(x(), 10.);
+}
+
+#[test]
+fn lens_macro_on_rc() {
+    let p = Rc::new(RefCell::new(player()));
+    moves(lens!(Player, pos.x -> f64).on_rc(p.clone()), || p.borrow().pos.x);
+    assert_eq!(p.borrow().pos.y, 5.);
+}
+
+#[test]
+fn lenses_macro_on_rc() {
+    let p = Rc::new(RefCell::new(player()));
+    moves(Player::x().on_rc(p.clone()), || p.borrow().pos.x);
+    let mut alpha = Player::alpha().on_rc(p.clone());
+    alpha.set(0.5);
+    assert_eq!(p.borrow().alpha, 0.5);
+}
+
+#[test]
+fn on_cell() {
+    let p = Cell::new(player());
+    let mut tw = from_to(Player::x().on_cell(&p), 0., 10., linear(), In, 1.);
+    tw.update(0.5);
+    assert_eq!(p.get().pos.x, 5.);
+    tw.update(0.5);
+    assert_eq!(p.get().pos.x, 10.);
+    assert_eq!(p.get().pos.y, 5.);
+}
+
+#[test]
+#[should_fail]
+fn rc_lens_while_borrowed() {
+    let p = Rc::new(RefCell::new(player()));
+    let mut tw = from_to(Player::x().on_rc(p.clone()), 0., 10., linear(), In, 1.);
+    let _held = p.borrow();
+    tw.update(0.5);
+}
+
+struct Ball {
+    x: f64
+}
+
+fn get_x(b: &Ball) -> f64 {
+    b.x
+}
+
+fn set_x(b: &mut Ball, x: f64) {
+    b.x = x;
+}
+
+fn x() -> Lens<Ball, f64> {
+    Lens {get: get_x, set: set_x}
+}
+
+#[test]
+fn removed_before_first_update() {
+    let arena = Arena::new();
+    let key = arena.insert(Ball {x: 5.});
+    let mut tw = from_to(x().on_slot(&arena, key.clone()), 0., 10., linear(), In, 1.);
+    assert!(arena.remove(key.clone()).is_some());
+    tw.update(0.5);
+    tw.update(0.5);
+    assert!(tw.done());
+    assert!(arena.with(key, |b| b.x).is_none());
+}
+
+#[test]
+fn reads_last_value_after_removal() {
+    let arena = Arena::new();
+    let key = arena.insert(Ball {x: 5.});
+    let mut acc = x().on_slot(&arena, key.clone());
+    let copy = acc.clone();
+    assert_eq!(acc.get(), 5.);
+    acc.set(7.);
+    arena.remove(key);
+    assert_eq!(acc.get(), 7.);
+    assert_eq!(copy.get(), 7.);
+    acc.set(9.);
+    assert_eq!(acc.get(), 9.);
+}
+
+#[test]
+fn reused_slot_is_not_written() {
+    let arena = Arena::new();
+    let key = arena.insert(Ball {x: 5.});
+    let mut acc = x().on_slot(&arena, key.clone());
+    arena.remove(key);
+    let other = arena.insert(Ball {x: 1.});
+    acc.set(3.);
+    assert_eq!(arena.with(other, |b| b.x), Some(1.));
+}
@@ -0,0 +1,169 @@
+//! Reading the Lottie files in `tests/lottie` and playing them back.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use tween::Tween;
+use tween::lottie::{Animation, Invalid};
+
+fn near(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-3
+}
+
+/// Play the whole animation into one value per bound dimension.
+fn bind(anim: &Animation, name: &str, dim: uint) -> (Box<Tween + 'static>, Rc<Cell<f64>>) {
+    let val = Rc::new(Cell::new(-1.));
+    let tw = anim.tween(|n, d| if n == name && d == dim {Some(val.clone())} else {None});
+    (tw, val)
+}
+
+#[test]
+fn static_values() {
+    let anim = Animation::from_str(include_str!("lottie/static.json")).unwrap();
+    assert!(near(anim.duration(), 2.));
+    let layer = &anim.layers[0];
+    assert_eq!(layer.name.as_slice(), "Still");
+
+    let pos = &layer.properties[0];
+    assert_eq!(pos.name.as_slice(), "position");
+    let vals: Vec<f64> = pos.dims.iter().map(|c| c.value_at(1.)).collect();
+    assert_eq!(vals, vec![10., 20., 0.]);
+    assert!(!pos.dims[0].is_animated());
+    assert_eq!(layer.properties[1].dims[0].value_at(0.), 50.);
+
+    let found = layer.tween(|_, _| Some(Rc::new(Cell::new(0.))));
+    assert!(found.is_none());
+}
+
+#[test]
+fn animated_values() {
+    let anim = Animation::from_str(include_str!("lottie/animated.json")).unwrap();
+    let curve = &anim.layers[0].properties[0].dims[0];
+    assert!(near(curve.duration(), 2.));
+    assert!(near(curve.value_at(0.), 0.));
+    assert!(near(curve.value_at(0.5), 50.));
+    assert!(near(curve.value_at(1.), 100.));
+    assert!(near(curve.value_at(1.5), 70.));
+    assert!(near(curve.value_at(5.), 40.));
+
+    let (mut tw, val) = bind(&anim, "Fade.opacity", 0);
+    tw.update(0.5);
+    assert!(near(val.get(), 50.));
+    tw.update(1.5);
+    assert!(near(val.get(), 40.));
+    assert!(tw.done());
+}
+
+#[test]
+fn hold_keyframes() {
+    let anim = Animation::from_str(include_str!("lottie/hold.json")).unwrap();
+    let curve = &anim.layers[0].properties[0].dims[0];
+    assert_eq!(curve.value_at(0.), 0.);
+    assert_eq!(curve.value_at(0.99), 0.);
+    assert_eq!(curve.value_at(1.), 100.);
+    assert_eq!(curve.value_at(1.99), 100.);
+    assert_eq!(curve.value_at(3.), 100.);
+}
+
+#[test]
+fn boolean_flags() {
+    let anim = Animation::from_str(include_str!("lottie/flags.json")).unwrap();
+    let props = &anim.layers[0].properties;
+    assert_eq!(props[0].name.as_slice(), "rotation");
+    assert!(!props[0].dims[0].is_animated());
+    assert_eq!(props[0].dims[0].value_at(1.), 45.);
+
+    let curve = &props[1].dims[0];
+    assert!(curve.is_animated());
+    assert_eq!(curve.value_at(0.99), 0.);
+    assert_eq!(curve.value_at(1.), 100.);
+    assert!(near(curve.value_at(1.5), 75.));
+    assert!(near(curve.value_at(2.), 50.));
+}
+
+#[test]
+fn bezier_tangents() {
+    let anim = Animation::from_str(include_str!("lottie/bezier.json")).unwrap();
+    let pos = &anim.layers[0].properties[0];
+    assert_eq!(pos.dims.len(), 2);
+    let (x, y) = (&pos.dims[0], &pos.dims[1]);
+
+    // the temporal ease is symmetric, so halfway in time is halfway along
+    // the spatial curve
+    assert!(near(x.value_at(0.5), 50.));
+    assert!(near(y.value_at(0.5), 37.5));
+    // it eases in, so a quarter in time is less than a quarter of the way
+    assert!(x.value_at(0.25) < 25.);
+    assert!(near(x.value_at(1.), 100.));
+    assert!(near(y.value_at(1.), 0.));
+}
+
+#[test]
+fn layer_offsets() {
+    let anim = Animation::from_str(include_str!("lottie/offset.json")).unwrap();
+    let layer = &anim.layers[0];
+    assert!(near(layer.in_point, 0.5));
+    assert!(near(layer.start_time, 1.));
+
+    // the keyframes are at 0 and 1s in layer time, 1 and 2s in the
+    // composition, and the layer appears at 0.5s
+    let (mut tw, val) = bind(&anim, "Late.opacity", 0);
+    assert!(near(tw.remaining(), 2.));
+    tw.update(0.25);
+    assert_eq!(val.get(), -1.);
+    tw.update(0.75);
+    assert!(near(val.get(), 0.));
+    tw.update(0.5);
+    assert!(near(val.get(), 50.));
+    tw.update(0.5);
+    assert!(near(val.get(), 100.));
+    assert!(tw.done());
+}
+
+#[test]
+fn split_position() {
+    let anim = Animation::from_str(include_str!("lottie/split.json")).unwrap();
+    let pos = &anim.layers[0].properties[0];
+    assert_eq!(pos.name.as_slice(), "position");
+    assert_eq!(pos.dims.len(), 2);
+    assert!(pos.dims[0].is_animated());
+    assert!(near(pos.dims[0].value_at(0.5), 50.));
+    assert_eq!(pos.dims[1].value_at(0.5), 20.);
+
+    let (mut tw, val) = bind(&anim, "Split.position", 0);
+    tw.update(0.5);
+    assert!(near(val.get(), 50.));
+    tw.update(0.5);
+    assert!(near(val.get(), 100.));
+    assert!(tw.done());
+}
+
+#[test]
+fn time_remap() {
+    let anim = Animation::from_str(include_str!("lottie/time_remap.json")).unwrap();
+    let remap = anim.layers[0].time_remap.as_ref().unwrap();
+    assert!(near(remap.value_at(0.), 1.));
+    assert!(near(remap.value_at(2.), 0.));
+
+    // the remap plays the one second of opacity backwards over two
+    let (mut tw, val) = bind(&anim, "Rewind.opacity", 0);
+    assert!(near(tw.remaining(), 2.));
+    tw.update(0.5);
+    assert!(near(val.get(), 75.));
+    tw.update(0.5);
+    assert!(near(val.get(), 50.));
+    tw.update(1.);
+    assert!(near(val.get(), 0.));
+    assert!(tw.done());
+}
+
+#[test]
+fn empty_keyframe_value() {
+    match Animation::from_str(include_str!("lottie/empty_value.json")) {
+        Err(Invalid(key)) => assert_eq!(key, "s"),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(_) => panic!("empty keyframe value was accepted")
+    }
+}
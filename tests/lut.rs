@@ -0,0 +1,27 @@
+//! Easings from tables of samples.
+
+extern crate tween;
+
+use tween::ease::Ease;
+use tween::lut::{sampled, sampled_monotonic, Linear, Cubic, Decreasing};
+
+#[test]
+fn held_outside_the_points() {
+    for interp in [Linear, Cubic].iter() {
+        let e = sampled(&[(0.2, 0.), (0.5, 0.7), (0.8, 1.)], interp.clone()).unwrap();
+        assert_eq!(e.ease_in(0.1), 0.);
+        assert_eq!(e.ease_in(0.9), 1.);
+        assert_eq!(e.velocity_in(0.1), 0.);
+        assert_eq!(e.velocity_in(0.9), 0.);
+        assert!(e.velocity_in(0.5) > 0.);
+    }
+}
+
+#[test]
+fn monotonic() {
+    assert!(sampled_monotonic(&[(0., 0.), (0.5, 0.5), (1., 1.)], Cubic).is_ok());
+    match sampled_monotonic(&[(0., 0.), (0.5, 1.2), (1., 1.)], Cubic) {
+        Err(e) => assert!(e == Decreasing(2)),
+        Ok(_) => panic!("a curve going back was accepted")
+    }
+}
@@ -0,0 +1,95 @@
+//! Waiting for tweens owned by a `Manager`.
+
+extern crate tween;
+
+use tween::pause;
+use tween::manager::Manager;
+
+#[test]
+fn finish_in_same_update() {
+    let mut m = Manager::new();
+    let id = m.add(pause(0.5));
+    let rx = m.on_finish(id);
+    m.update(0.5);
+    assert!(m.is_finished(id));
+    assert!(rx.recv_opt().is_ok());
+}
+
+#[test]
+fn already_finished() {
+    let mut m = Manager::new();
+    let id = m.add(pause(0.5));
+    m.update(1.);
+    assert!(!m.contains(id));
+    assert!(m.on_finish(id).recv_opt().is_ok());
+}
+
+#[test]
+fn removed_or_unknown() {
+    let mut m = Manager::new();
+    let id = m.add(pause(0.5));
+    let rx = m.on_finish(id);
+    assert!(m.remove(id).is_some());
+    assert!(!m.is_finished(id));
+    assert!(rx.recv_opt().is_err());
+    assert!(m.on_finish(id).recv_opt().is_err());
+
+    let mut other = Manager::new();
+    other.add(pause(1.));
+    let unknown = other.add(pause(1.));
+    assert!(m.on_finish(unknown).recv_opt().is_err());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn futures() {
+    use tween::async::Completion;
+
+    let mut m = Manager::new();
+    let done = m.add(pause(0.5));
+    let removed = m.add(pause(0.5));
+    let mut pending = m.finished(done);
+    assert_eq!(pending.poll(), None);
+    m.remove(removed);
+    m.update(0.5);
+    assert_eq!(pending.poll(), Some(true));
+    assert!(pending.get());
+    assert!(m.finished(done).get());
+    assert!(!m.finished(removed).get());
+    assert!(m.finished(done).into_future().unwrap());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn wait_on_another_task() {
+    use tween::async::Completion;
+
+    let mut m = Manager::new();
+    let id = m.add(pause(1.));
+    let finished = m.finished(id);
+    let (tx, rx) = channel();
+    spawn(proc() tx.send(finished.get()));
+    // the waiting task doesn't hold up the one updating
+    for _ in range(0u, 4) {
+        assert!(rx.try_recv().is_err());
+        m.update(0.25);
+    }
+    assert!(rx.recv());
+}
+
+#[test]
+fn forgets_old_removals() {
+    let mut m = Manager::new();
+    let first = m.add(pause(0.5));
+    m.remove(first);
+    for _ in range(0u, 1000) {
+        let id = m.add(pause(0.5));
+        m.remove(id);
+    }
+    m.update(0.5);
+    // pruned, but still not reported as finished
+    assert!(!m.is_finished(first));
+    let done = m.add(pause(0.5));
+    m.update(0.5);
+    assert!(m.is_finished(done));
+}
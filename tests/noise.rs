@@ -0,0 +1,59 @@
+//! Seeded noise offsets.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::f64::INFINITY;
+use std::rc::Rc;
+
+use tween::Tween;
+use tween::ease::{linear, In};
+use tween::noise::{noise, shake, Kind, Value, Perlin, RandomWalk};
+
+/// Play noise of `kind` with `seed` for two time units, reading the value
+/// every 1/60th.
+fn record(kind: Kind, seed: u32) -> Vec<f64> {
+    let val = Rc::new(Cell::new(0.));
+    let mut tw = noise(kind, vec![val.clone()], 3., 10., seed, linear(), In, INFINITY);
+    range(0u, 120).map(|_| { tw.update(1. / 60.); val.get() }).collect()
+}
+
+#[test]
+fn same_seed_same_values() {
+    for kind in [Value, Perlin, RandomWalk].iter() {
+        let a = record(kind.clone(), 7);
+        assert_eq!(a, record(kind.clone(), 7));
+        assert!(a != record(kind.clone(), 8));
+    }
+}
+
+#[test]
+fn within_amplitude() {
+    for kind in [Value, Perlin, RandomWalk].iter() {
+        for v in record(kind.clone(), 3).iter() {
+            assert!(v.abs() <= 3. + 1e-9, "{} is out of bounds", v);
+        }
+    }
+}
+
+#[test]
+fn walk_backwards() {
+    let val = Rc::new(Cell::new(0.));
+    let mut tw = noise(RandomWalk, vec![val.clone()], 1., 10., 42, linear(), In, INFINITY);
+    tw.update(0.35);
+    let before = val.get();
+    tw.update(0.5);
+    tw.update(-0.5);
+    assert!((val.get() - before).abs() < 1e-9);
+}
+
+#[test]
+fn shake_without_duration() {
+    let val = Rc::new(Cell::new(5.));
+    let mut tw = shake(vec![val.clone()], 3., 10., 7, linear(), 0.);
+    assert!(tw.done());
+    tw.update(0.);
+    assert_eq!(val.get(), 5.);
+    tw.update(0.25);
+    assert_eq!(val.get(), 5.);
+}
@@ -0,0 +1,78 @@
+//! Drawing tween trees. The Gantt chart is compared with the SVG in
+//! `tests/golden`, which is rewritten by running the tests with
+//! `TWEEN_BLESS` set.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::io::File;
+use std::os;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+
+use tween::{Tween, from_to, seq, par, rep, pause, exec};
+use tween::ease::{linear, In};
+use tween::plot::{Style, gantt, sample};
+
+fn nothing() {}
+
+#[test]
+fn gantt_golden() {
+    let mut tw = par(vec![
+        seq(vec![pause(2.), exec(nothing), pause(3.)]),
+        rep(pause(2.5))
+    ]);
+    tw.update(3.);
+    let style = Style {width: 810., row: 20., window: Some(10.), ..Style::new()};
+    let svg = gantt(&tw.describe(), &style);
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join_many(["tests", "golden", "gantt.svg"]);
+    if os::getenv("TWEEN_BLESS").is_some() {
+        File::create(&path).write_str(svg.as_slice()).unwrap();
+        return;
+    }
+    let expected = File::open(&path).read_to_string().unwrap();
+    assert_eq!(svg, expected);
+}
+
+#[test]
+fn sample_follows_the_ease() {
+    let val = Rc::new(Cell::new(7.));
+    let tw = box from_to(val.clone(), 0., 10., linear(), In, 1.) as Box<Tween + 'static>;
+    let curves = sample(&tw, &mut [val.clone()], 1., 4);
+
+    let curve = &curves[0];
+    assert_eq!(curve.len(), 5);
+    // nothing is written before the first update
+    assert_eq!(curve[0], (0., 7.));
+    assert_eq!(curve[1], (0.25, 2.5));
+    assert_eq!(curve[2], (0.5, 5.));
+    assert_eq!(curve[4], (1., 10.));
+    // the target is set back afterwards
+    assert_eq!(val.get(), 7.);
+}
+
+static CALLS: AtomicUint = INIT_ATOMIC_UINT;
+
+fn count() {
+    CALLS.fetch_add(1, SeqCst);
+}
+
+#[test]
+fn sample_nested() {
+    let (x, y) = (Rc::new(Cell::new(0.)), Rc::new(Cell::new(0.)));
+    let tw = par(vec![
+        seq(vec![
+            box from_to(x.clone(), 0., 10., linear(), In, 1.) as Box<Tween + 'static>,
+            box from_to(x.clone(), 10., 20., linear(), In, 1.) as Box<Tween + 'static>
+        ]),
+        box from_to(y.clone(), 0., 4., linear(), In, 2.) as Box<Tween + 'static>,
+        exec(count)
+    ]);
+    let curves = sample(&tw, &mut [x.clone(), y.clone()], 2., 4);
+
+    assert_eq!(curves[0], vec![(0., 0.), (0.5, 5.), (1., 10.), (1.5, 15.), (2., 20.)]);
+    assert_eq!(curves[1], vec![(0., 0.), (0.5, 1.), (1., 2.), (1.5, 3.), (2., 4.)]);
+    // the `Exec` is only called once
+    assert_eq!(CALLS.load(SeqCst), 1);
+}
@@ -0,0 +1,145 @@
+//! Updating tweens on a pool of tasks.
+
+#![cfg(feature = "threads")]
+
+extern crate tween;
+
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+
+use tween::{Tween, Pause, from_to, par, rep};
+use tween::ease::{linear, quad, In, InOut};
+use tween::pool::{Pool, SendTween, concurrent, send_exec, send_seq, send_rep};
+
+fn single(val: &Arc<Mutex<f64>>, duration: f64) -> Box<SendTween + Send> {
+    box from_to(val.clone(), 0., 1., linear(), In, duration) as Box<SendTween + Send>
+}
+
+fn pause(time: f64) -> Box<SendTween + Send> {
+    box Pause::new(time) as Box<SendTween + Send>
+}
+
+/// The same children in a plain `Parallel`.
+fn plain(tweens: Vec<Box<SendTween + Send>>) -> Box<Tween + 'static> {
+    par(tweens.into_iter().map(|tw| box tw as Box<Tween + 'static>).collect())
+}
+
+#[test]
+fn updates_all_children() {
+    let pool = Rc::new(Pool::new(2));
+    let vals: Vec<Arc<Mutex<f64>>> = range(0u, 5).map(|_| Arc::new(Mutex::new(-1.))).collect();
+    let mut tw = concurrent(pool, vals.iter().map(|v| single(v, 1.)).collect());
+    tw.update(0.5);
+    tw.update(0.5);
+    assert!(tw.done());
+    for v in vals.iter() {
+        assert_eq!(*v.lock(), 1.);
+    }
+}
+
+#[test]
+fn repeats() {
+    let pool = Rc::new(Pool::new(2));
+    let val = Arc::new(Mutex::new(0.));
+    let mut r = rep(concurrent(pool, vec![single(&val, 1.), single(&val, 0.5)]));
+    r.update(0.6);
+    r.update(0.6);
+    let node = r.describe();
+    assert_eq!(node.repeats, Some(1));
+    assert!((node.children[0].elapsed - 0.2).abs() < 1e-9);
+}
+
+#[deriving(Clone)]
+struct Boom;
+
+impl Tween for Boom {
+    fn remaining(&self) -> f64 {
+        1.
+    }
+
+    fn reset(&mut self) {}
+
+    fn update(&mut self, _delta: f64) -> f64 {
+        panic!("boom")
+    }
+}
+
+#[test]
+#[should_fail]
+fn panics_when_a_worker_does() {
+    let pool = Rc::new(Pool::new(2));
+    let val = Arc::new(Mutex::new(0.));
+    let mut tw = concurrent(pool, vec![single(&val, 1.), box Boom as Box<SendTween + Send>]);
+    tw.update(0.5);
+}
+
+/// Every callback appends its digit.
+static ORDER: AtomicUint = INIT_ATOMIC_UINT;
+
+fn log(n: uint) {
+    ORDER.store(ORDER.load(SeqCst) * 10 + n, SeqCst);
+}
+
+fn one() { log(1) }
+fn two() { log(2) }
+fn three() { log(3) }
+fn four() { log(4) }
+fn five() { log(5) }
+
+fn callbacks() -> Vec<Box<SendTween + Send>> {
+    vec![
+        send_seq(vec![send_exec(one), pause(0.5), send_exec(two)]),
+        send_exec(three),
+        send_seq(vec![pause(0.25), send_exec(four)]),
+        send_rep(send_seq(vec![pause(0.5), send_exec(five)])),
+        send_exec(one)
+    ]
+}
+
+/// Play `tw` for a second in two updates, returning the digits logged.
+fn play(tw: &mut Box<Tween + 'static>) -> uint {
+    ORDER.store(0, SeqCst);
+    tw.update(0.5);
+    tw.update(0.5);
+    ORDER.load(SeqCst)
+}
+
+#[test]
+fn callbacks_in_parallel_order() {
+    let expected = play(&mut plain(callbacks()));
+    // per update, each child's calls in the order of the children, and
+    // finished ones aren't called again
+    assert_eq!(expected, 1234515);
+
+    let pool = Rc::new(Pool::new(3));
+    for _ in range(0u, 20) {
+        assert_eq!(play(&mut concurrent(pool.clone(), callbacks())), expected);
+    }
+}
+
+fn tree(vals: &[Arc<Mutex<f64>>]) -> Vec<Box<SendTween + Send>> {
+    vec![
+        single(&vals[0], 1.),
+        box from_to(vals[1].clone(), 5., -5., quad(), InOut, 0.75) as Box<SendTween + Send>,
+        send_seq(vec![single(&vals[2], 0.5), pause(0.25), single(&vals[2], 0.5)]),
+        send_rep(send_seq(vec![single(&vals[3], 0.4), pause(0.1)])),
+        single(&vals[4], 0.)
+    ]
+}
+
+#[test]
+fn same_as_parallel() {
+    let a: Vec<Arc<Mutex<f64>>> = range(0u, 5).map(|_| Arc::new(Mutex::new(-1.))).collect();
+    let b: Vec<Arc<Mutex<f64>>> = range(0u, 5).map(|_| Arc::new(Mutex::new(-1.))).collect();
+    let mut p = plain(tree(a.as_slice()));
+    let mut c = concurrent(Rc::new(Pool::new(2)), tree(b.as_slice()));
+
+    for &delta in [0.1, 0.3, 0.25, 0.6, 0.05, 1.].iter() {
+        assert_eq!(p.update(delta), c.update(delta));
+        assert_eq!(p.remaining(), c.remaining());
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert_eq!(*x.lock(), *y.lock());
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Tweening several properties along one timeline.
+
+#![feature(phase)]
+
+#[phase(plugin, link)]
+extern crate tween;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use tween::Tween;
+use tween::ease::{Ease, In};
+
+/// A quadratic ease that counts how often it is evaluated.
+#[deriving(Clone)]
+struct Counting {
+    calls: Rc<Cell<uint>>
+}
+
+impl Ease for Counting {
+    fn ease_in(&self, t: f64) -> f64 {
+        self.calls.set(self.calls.get() + 1);
+        t * t
+    }
+}
+
+#[test]
+fn to_props_() {
+    let (x, y, alpha) = (Rc::new(Cell::new(0.)), Rc::new(Cell::new(10.)), Rc::new(Cell::new(1f32)));
+    let calls = Rc::new(Cell::new(0u));
+    let mut tw = to_props!(Counting {calls: calls.clone()}, In, 2., [
+        x.clone() => 100.,
+        y.clone() => 50.,
+        alpha.clone() => 0f32
+    ]);
+
+    tw.update(0.5);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(x.get(), 6.25);
+    assert_eq!(y.get(), 12.5);
+    assert_eq!(alpha.get(), 0.9375);
+
+    tw.update(0.5);
+    assert_eq!(calls.get(), 2);
+    assert_eq!(x.get(), 25.);
+    assert_eq!(y.get(), 20.);
+    assert_eq!(alpha.get(), 0.75);
+
+    tw.update(1.);
+    assert!(tw.done());
+    assert_eq!(calls.get(), 3);
+    assert_eq!(x.get(), 100.);
+    assert_eq!(y.get(), 50.);
+    assert_eq!(alpha.get(), 0.);
+}
+
+#[test]
+fn zero_duration() {
+    let (x, alpha) = (Rc::new(Cell::new(0.)), Rc::new(Cell::new(1f32)));
+    let calls = Rc::new(Cell::new(0u));
+    let mut tw = to_props!(Counting {calls: calls.clone()}, In, 0., [
+        x.clone() => 100.,
+        alpha.clone() => 0f32
+    ]);
+    assert!(tw.done());
+    tw.update(0.);
+    assert_eq!(x.get(), 100.);
+    assert_eq!(alpha.get(), 0.);
+}
@@ -0,0 +1,90 @@
+//! Looking up easings by name, and the CSS step easings.
+
+extern crate tween;
+
+use tween::ease::{Ease, In, InOut, quint, bezier, steps};
+use tween::registry::{Registry, MAX_BOUNCES};
+
+static TIMES: [f64, ..5] = [0., 0.2, 0.5, 0.7, 1.];
+
+#[test]
+fn penner_name() {
+    let r = Registry::with_builtins();
+    let (e, mode) = r.lookup("easeInOutQuint").unwrap();
+    assert_eq!(mode, InOut);
+    assert_eq!(e.name(), "quint".to_string());
+    for &t in TIMES.iter() {
+        assert_eq!(e.ease(mode, t), quint().ease(InOut, t));
+    }
+}
+
+#[test]
+fn css_cubic_bezier() {
+    let r = Registry::with_builtins();
+    let (e, mode) = r.lookup("cubic-bezier(.17,.67,.83,.67)").unwrap();
+    assert_eq!(mode, In);
+    assert_eq!(e.name(), "bezier(0.17, 0.67, 0.83, 0.67)".to_string());
+    let expected = bezier(0.17, 0.67, 0.83, 0.67);
+    for &t in TIMES.iter() {
+        assert_eq!(e.ease(In, t), expected.ease(In, t));
+    }
+}
+
+#[test]
+fn unknown_names() {
+    let r = Registry::with_builtins();
+    assert!(r.lookup("wobble").is_none());
+    assert!(r.lookup("easeInOutWobble").is_none());
+    assert!(r.lookup("cubic-bezier(1.5, 0, 0, 1)").is_none());
+    assert!(r.lookup("cubic-bezier(.17, .67)").is_none());
+    assert!(r.lookup("steps(0)").is_none());
+}
+
+#[test]
+fn bounce_limit() {
+    let r = Registry::with_builtins();
+    assert!(r.lookup("bounce(3)").is_some());
+    assert!(r.lookup(format!("bounce({})", MAX_BOUNCES).as_slice()).is_some());
+    assert!(r.lookup(format!("bounce({}, 0.5)", MAX_BOUNCES + 1).as_slice()).is_none());
+    assert!(r.lookup("bounce(1e300)").is_none());
+    assert!(r.lookup("bounce(2.5)").is_none());
+}
+
+#[test]
+fn steps_end() {
+    let e = steps(4);
+    assert_eq!(e.ease_in(0.), 0.);
+    assert_eq!(e.ease_in(0.2), 0.);
+    assert_eq!(e.ease_in(0.25), 0.25);
+    assert_eq!(e.ease_in(0.6), 0.5);
+    assert_eq!(e.ease_in(0.99), 0.75);
+    assert_eq!(e.ease_in(1.), 1.);
+    assert_eq!(e.velocity(In, 0.6), 0.);
+}
+
+#[test]
+fn steps_start() {
+    let e = steps(4).start();
+    assert_eq!(e.ease_in(0.), 0.);
+    assert_eq!(e.ease_in(0.01), 0.25);
+    assert_eq!(e.ease_in(0.25), 0.25);
+    assert_eq!(e.ease_in(0.6), 0.75);
+    assert_eq!(e.ease_in(1.), 1.);
+}
+
+#[test]
+fn css_steps() {
+    let r = Registry::with_builtins();
+    let (e, _) = r.lookup("steps(4, start)").unwrap();
+    assert_eq!(e.name(), "steps(4, start)".to_string());
+    let (end, _) = r.lookup("step-end").unwrap();
+    assert_eq!(end.name(), "steps(1)".to_string());
+    let (start, _) = r.lookup("step-start").unwrap();
+    assert_eq!(start.name(), "steps(1, start)".to_string());
+}
+
+#[test]
+#[should_fail]
+fn no_steps() {
+    steps(0);
+}
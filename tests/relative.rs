@@ -0,0 +1,77 @@
+//! Relative and additive tweens stacking on the same property.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::mem;
+use std::rc::Rc;
+
+use tween::{Tween, by, additive, par};
+use tween::ease::{linear, quad, In, Out};
+use tween::noise::shake;
+
+/// Play a move `by` 100 and a shake on a value starting at 10, either or
+/// both, recording the value every 1/60th for a second.
+fn play(move_: bool, shake_: bool) -> Vec<f64> {
+    let val = Rc::new(Cell::new(10.));
+    let mut tweens = Vec::new();
+    if move_ {
+        tweens.push(box by(val.clone(), 100., quad(), Out, 0.75) as Box<Tween + 'static>);
+    }
+    if shake_ {
+        tweens.push(box shake(vec![val.clone()], 4., 12., 3, linear(), 0.5) as Box<Tween + 'static>);
+    }
+    let mut tw = par(tweens);
+    range(0u, 60).map(|_| { tw.update(1. / 60.); val.get() }).collect()
+}
+
+#[test]
+fn move_and_shake_add_up() {
+    let (both, moved, shaken) = (play(true, true), play(true, false), play(false, true));
+    for i in range(0, both.len()) {
+        let sum = moved[i] + shaken[i] - 10.;
+        assert!((both[i] - sum).abs() < 1e-9, "frame {}: {} instead of {}", i, both[i], sum);
+    }
+    // the shake ends at no offset, and the move at its full distance
+    assert!((both[59] - 110.).abs() < 1e-9);
+}
+
+#[test]
+fn move_and_shake_are_deterministic() {
+    let bits = |v: Vec<f64>| -> Vec<u64> {
+        v.into_iter().map(|x| unsafe { mem::transmute::<f64, u64>(x) }).collect()
+    };
+    assert_eq!(bits(play(true, true)), bits(play(true, true)));
+}
+
+#[test]
+fn additive_offsets_stack() {
+    let val = Rc::new(Cell::new(1.));
+    let mut tw = par(vec![
+        box additive(val.clone(), 0., 2., linear(), In, 1.) as Box<Tween + 'static>,
+        box additive(val.clone(), 0., -6., linear(), In, 0.5) as Box<Tween + 'static>,
+        box by(val.clone(), 4., linear(), In, 1.) as Box<Tween + 'static>
+    ]);
+    tw.update(0.25);
+    assert_eq!(val.get(), 1. + 0.5 - 3. + 1.);
+    tw.update(0.25);
+    assert_eq!(val.get(), 1. + 1. - 6. + 2.);
+    tw.update(0.5);
+    assert_eq!(val.get(), 1. + 2. - 6. + 4.);
+}
+
+#[test]
+fn zero_duration() {
+    let val = Rc::new(Cell::new(1.));
+    let mut tw = by(val.clone(), 5., linear(), In, 0.);
+    assert!(tw.done());
+    assert_eq!(tw.update(0.25), 0.25);
+    assert_eq!(val.get(), 6.);
+    // the whole offset is applied once
+    tw.update(0.25);
+    assert_eq!(val.get(), 6.);
+
+    let mut tw = additive(val.clone(), 0., 2., linear(), In, 0.);
+    tw.update(0.);
+    assert_eq!(val.get(), 8.);
+}
@@ -0,0 +1,18 @@
+//! Repeating tweens a given number of times.
+
+extern crate tween;
+
+use tween::{Tween, Repeat, pause};
+
+#[test]
+fn counted() {
+    let mut r = Repeat::times(pause(1.), 3);
+    assert_eq!(r.remaining(), 3.);
+    assert!(r.update(2.5) < 0.);
+    assert_eq!(r.remaining(), 0.5);
+    assert_eq!(r.update(1.), 0.5);
+    assert!(r.done());
+    let node = r.describe();
+    assert_eq!(node.repeats, Some(3));
+    assert_eq!(node.children[0].elapsed, 1.);
+}
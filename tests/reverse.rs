@@ -0,0 +1,25 @@
+//! Playing tweens backwards.
+
+extern crate tween;
+
+use std::sync::atomic::{AtomicUint, INIT_ATOMIC_UINT, SeqCst};
+
+use tween::{Tween, seq, pause, exec, yoyo};
+
+static CALLS: AtomicUint = INIT_ATOMIC_UINT;
+
+fn count() {
+    CALLS.fetch_add(1, SeqCst);
+}
+
+#[test]
+fn reset_does_not_replay() {
+    let mut tw = yoyo(seq(vec![pause(1.), exec(count)]));
+    // the reversed copy is played to its end once when it is built
+    assert_eq!(CALLS.load(SeqCst), 1);
+    for _ in range(0u, 4) {
+        tw.update(1.);
+    }
+    // once per cycle forwards, and never when rewinding
+    assert_eq!(CALLS.load(SeqCst), 3);
+}
@@ -0,0 +1,64 @@
+//! Updating tweens on their own task. Most runners here wait on a
+//! `ManualClock`, so the tests don't depend on how fast they run.
+
+extern crate tween;
+
+use std::sync::{Arc, Mutex};
+
+use tween::{Tween, from_to, pause};
+use tween::ease::{linear, In};
+use tween::clock::ManualClock;
+use tween::runner::Runner;
+
+#[test]
+fn runs_to_the_end() {
+    let r = Runner::spawn(100., proc() pause(0.05));
+    r.start();
+    assert!(r.join());
+}
+
+#[test]
+fn manual_clock() {
+    let val = Arc::new(Mutex::new(0f64));
+    let v = val.clone();
+    let r = Runner::spawn_with_clock(ManualClock::new(), 100.,
+        proc() box from_to(v, 0., 1., linear(), In, 30.) as Box<Tween + 'static>);
+    r.start();
+    // 3000 ticks, without waiting for them
+    assert!(r.join());
+    assert_eq!(*val.lock(), 1.);
+}
+
+#[test]
+fn stop_and_resume() {
+    let val = Arc::new(Mutex::new(0f64));
+    let v = val.clone();
+    let r = Runner::spawn_with_clock(ManualClock::new(), 100.,
+        proc() box from_to(v, 0., 1., linear(), In, 0.3) as Box<Tween + 'static>);
+    r.start();
+    // where the stop lands depends on the task, only its results are checked
+    r.stop();
+    let stopped = *val.lock();
+    assert!(stopped >= 0. && stopped <= 1., "at {} when stopped", stopped);
+    r.start();
+    assert!(r.join());
+    assert!(*val.lock() >= stopped);
+    assert_eq!(*val.lock(), 1.);
+}
+
+#[test]
+fn join_stopped() {
+    let r = Runner::spawn(100., proc() pause(1.));
+    assert!(!r.join());
+
+    let r = Runner::spawn(100., proc() pause(1.));
+    r.start();
+    r.stop();
+    assert!(!r.join());
+}
+
+#[test]
+#[should_fail]
+fn zero_rate() {
+    Runner::spawn(0., proc() pause(1.));
+}
@@ -0,0 +1,55 @@
+//! Tweening values behind `Rc<Cell<T>>` and `Rc<RefCell<T>>`.
+
+extern crate tween;
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use tween::{Tween, from_to, seq, pause};
+use tween::ease::{linear, In};
+
+#[test]
+fn rc_cell_in_seq() {
+    let val = Rc::new(Cell::new(0.));
+    let mut tw = seq(vec![
+        pause(0.5),
+        box from_to(val.clone(), 0., 10., linear(), In, 1.) as Box<Tween + 'static>
+    ]);
+    tw.update(1.);
+    assert_eq!(val.get(), 5.);
+    tw.update(0.5);
+    assert_eq!(val.get(), 10.);
+}
+
+#[test]
+fn rc_ref_cell_in_seq() {
+    let val = Rc::new(RefCell::new(0.));
+    let mut tw = seq(vec![
+        pause(0.5),
+        box from_to(val.clone(), 0., 10., linear(), In, 1.) as Box<Tween + 'static>
+    ]);
+    tw.update(1.);
+    assert_eq!(*val.borrow(), 5.);
+    // borrowing in between updates is fine
+    *val.borrow_mut() = 7.;
+    tw.update(0.5);
+    assert_eq!(*val.borrow(), 10.);
+}
+
+#[test]
+#[should_fail]
+fn ref_cell_mutably_borrowed() {
+    let val = Rc::new(RefCell::new(0.));
+    let mut tw = from_to(val.clone(), 0., 10., linear(), In, 1.);
+    let _held = val.borrow_mut();
+    tw.update(0.5);
+}
+
+#[test]
+#[should_fail]
+fn ref_cell_borrowed() {
+    let val = Rc::new(RefCell::new(0.));
+    let mut tw = from_to(val.clone(), 0., 10., linear(), In, 1.);
+    let _held = val.borrow();
+    tw.update(0.5);
+}
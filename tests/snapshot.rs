@@ -0,0 +1,97 @@
+//! Saving and restoring the state of tween trees.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::f64::INFINITY;
+use std::mem;
+use std::rc::Rc;
+
+use tween::{Tween, from_to, by, additive, seq, par, pause};
+use tween::ease::{linear, quad, sine, In, InOut};
+use tween::blend::crossfade;
+use tween::decay::decay;
+use tween::noise::{noise, RandomWalk};
+
+/// A tween from outside the crate that doesn't save anything.
+#[deriving(Clone)]
+struct Stateless;
+
+impl Tween for Stateless {
+    fn remaining(&self) -> f64 {0.}
+    fn reset(&mut self) {}
+    fn update(&mut self, delta: f64) -> f64 {delta}
+}
+
+#[test]
+fn default_save_and_load() {
+    let mut tw = seq(vec![pause(1.), box Stateless as Box<Tween + 'static>, pause(1.)]);
+    tw.update(0.5);
+    let snap = tw.snapshot();
+    tw.update(1.);
+    tw.restore(&snap);
+    assert_eq!(tw.remaining(), 1.5);
+}
+
+/// Play 30 frames, recording the bits of the value after each.
+fn frames(tw: &mut Box<Tween + 'static>, val: &Rc<Cell<f64>>) -> Vec<u64> {
+    range(0u, 30).map(|_| {
+        tw.update(1. / 60.);
+        unsafe { mem::transmute::<f64, u64>(val.get()) }
+    }).collect()
+}
+
+/// Play `tw` for a few frames, take a snapshot, and check that playing on
+/// after restoring it gives the same values, bit for bit. The value itself
+/// is not part of the snapshot, so it is saved along with it, like a game
+/// would.
+fn replays(mut tw: Box<Tween + 'static>, val: Rc<Cell<f64>>) {
+    for _ in range(0u, 10) {
+        tw.update(1. / 60.);
+    }
+    let snap = tw.snapshot();
+    let saved = val.get();
+    let first = frames(&mut tw, &val);
+
+    tw.restore(&snap);
+    val.set(saved);
+    let second = frames(&mut tw, &val);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn single() {
+    let val = Rc::new(Cell::new(0.));
+    replays(box from_to(val.clone(), 0., 100., sine(), InOut, 0.5) as Box<Tween + 'static>, val);
+}
+
+#[test]
+fn relative_and_additive() {
+    let val = Rc::new(Cell::new(10.));
+    replays(par(vec![
+        box by(val.clone(), 50., quad(), In, 0.4) as Box<Tween + 'static>,
+        box additive(val.clone(), 0., 3., sine(), InOut, 0.6) as Box<Tween + 'static>
+    ]), val);
+}
+
+#[test]
+fn random_walk() {
+    let val = Rc::new(Cell::new(0.));
+    replays(box noise(RandomWalk, vec![val.clone()], 3., 10., 7, linear(), In, INFINITY)
+            as Box<Tween + 'static>, val);
+}
+
+#[test]
+fn blend() {
+    let val = Rc::new(Cell::new(0.));
+    replays(box crossfade(vec![val.clone()],
+        |s| box from_to(s[0].clone(), 0., 10., linear(), In, 1.) as Box<Tween + 'static>,
+        |s| box from_to(s[0].clone(), 20., 40., quad(), In, 1.) as Box<Tween + 'static>,
+        0.5, sine(), InOut) as Box<Tween + 'static>, val);
+}
+
+#[test]
+fn decay_() {
+    let val = Rc::new(Cell::new(0.));
+    replays(box decay(val.clone(), 300., 4.).bounds(0., 50., 200.) as Box<Tween + 'static>, val);
+}
@@ -0,0 +1,67 @@
+//! Accessors for values shared between tasks, written by a runner and
+//! read here.
+
+extern crate tween;
+
+use std::sync::{Arc, Mutex, RWLock};
+
+use tween::{Access, Tween, Tweenable, from_to};
+use tween::ease::{linear, In};
+use tween::runner::Runner;
+use tween::sync::{AtomicF32, ChannelAccess};
+
+/// Tween `acc` from `start` to `end` on a runner, until it's done.
+fn run<T: Tweenable + Send + 'static, A: Access<T> + Send + 'static>(acc: A, start: T, end: T) {
+    let r = Runner::spawn(100., proc() {
+        box from_to(acc, start, end, linear(), In, 0.05) as Box<Tween + 'static>
+    });
+    r.start();
+    assert!(r.join());
+}
+
+#[test]
+#[cfg(target_word_size = "64")]
+fn atomic_f64() {
+    let val = tween::sync::AtomicF64::new(0.);
+    run(val.clone(), 0., 1.);
+    assert_eq!(val.load(), 1.);
+}
+
+#[test]
+fn atomic_f32() {
+    let val = AtomicF32::new(0.);
+    run(val.clone(), 0f32, 1.);
+    assert_eq!(val.load(), 1f32);
+}
+
+#[test]
+fn mutex() {
+    let val = Arc::new(Mutex::new(0f64));
+    run(val.clone(), 0., 1.);
+    assert_eq!(*val.lock(), 1.);
+}
+
+#[test]
+fn rwlock() {
+    let val = Arc::new(RWLock::new(0f64));
+    run(val.clone(), 0., 1.);
+    assert_eq!(*val.read(), 1.);
+}
+
+#[test]
+fn channel() {
+    let (tx, rx) = channel();
+    run(ChannelAccess::new(tx, 0f64), 0., 1.);
+    let sent: Vec<f64> = rx.iter().collect();
+    assert_eq!(sent.last(), Some(&1.));
+}
+
+#[test]
+fn channel_clones_share_the_last_value() {
+    let (tx, rx) = channel();
+    let mut a = ChannelAccess::new(tx, 0u);
+    let b = a.clone();
+    a.set(3);
+    assert_eq!(b.get(), 3);
+    assert_eq!(rx.recv(), 3);
+}
@@ -0,0 +1,98 @@
+//! The time every kind of tween returns from `update`: the time left over
+//! once it finished, or minus the time it still needs.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::f64::INFINITY;
+use std::rc::Rc;
+
+use tween::{Tween, Pause, Reverse, from_to, by, series, seq, par, pause, exec, rep};
+use tween::ease::{linear, In};
+
+fn val() -> Rc<Cell<f64>> {
+    Rc::new(Cell::new(0.))
+}
+
+/// Update a tween of one time unit by a quarter, then by a full unit.
+fn check<T: Tween>(mut tw: T) {
+    assert_eq!(tw.update(0.25), -0.75);
+    assert_eq!(tw.update(1.), 0.25);
+    assert!(tw.done());
+}
+
+#[test]
+fn single() {
+    check(from_to(val(), 0., 1., linear(), In, 1.));
+}
+
+#[test]
+fn relative() {
+    check(by(val(), 1., linear(), In, 1.));
+}
+
+#[test]
+fn multi() {
+    check(series(val(), vec![(0., 1., 0.5, In), (1., 0., 0.5, In)], linear()));
+}
+
+#[test]
+fn pause_() {
+    check(Pause::new(1.));
+}
+
+#[test]
+fn reverse() {
+    check(Reverse::new(pause(1.)));
+}
+
+#[test]
+fn sequence() {
+    let mut tw = seq(vec![pause(0.5), pause(0.5)]);
+    assert_eq!(tw.update(0.75), -0.25);
+    // the rest of the first pause is carried into the second
+    assert_eq!(tw.describe().children[1].elapsed, 0.25);
+    assert_eq!(tw.update(0.5), 0.25);
+}
+
+#[test]
+fn parallel() {
+    // counts from the longest child
+    let mut tw = par(vec![pause(0.5), pause(1.)]);
+    assert_eq!(tw.update(0.75), -0.25);
+    assert_eq!(tw.update(0.5), 0.25);
+}
+
+#[test]
+fn parallel_children_without_duration() {
+    // done from the start, but written by the first update
+    let (a, b) = (val(), val());
+    let mut tw = par(vec![
+        box from_to(a.clone(), 0., 3., linear(), In, 0.) as Box<Tween + 'static>,
+        box from_to(b.clone(), 0., 1., linear(), In, 1.) as Box<Tween + 'static>
+    ]);
+    assert_eq!(tw.update(0.5), -0.5);
+    assert_eq!(a.get(), 3.);
+    tw.reset();
+    a.set(0.);
+    tw.update(0.);
+    assert_eq!(a.get(), 3.);
+}
+
+fn nothing() {}
+
+#[test]
+fn exec_() {
+    assert_eq!(exec(nothing).update(0.5), 0.5);
+}
+
+#[test]
+fn repeat() {
+    let mut tw = rep(pause(1.));
+    assert_eq!(tw.update(0.6), -INFINITY);
+    assert_eq!(tw.update(0.6), -INFINITY);
+    // the rest of the first cycle is carried into the second
+    let node = tw.describe();
+    assert_eq!(node.repeats, Some(1));
+    assert!((node.children[0].elapsed - 0.2).abs() < 1e-9);
+}
@@ -0,0 +1,127 @@
+//! Velocities, checked against finite differences of the values.
+
+extern crate tween;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use tween::{Tween, from_to};
+use tween::ease::{Ease, In, Out, InOut, OutIn};
+use tween::ease::{linear, quad, cubic, quart, quint, power, sine, circ, back, elastic, bounce, bezier, boxed, DynEase};
+use tween::decay::decay;
+use tween::lottie::{Animation, Track};
+
+static H: f64 = 1e-6;
+
+/// Times away from the middle, where `InOut` and `OutIn` switch curves.
+static TIMES: [f64, ..6] = [0.1, 0.3, 0.45, 0.55, 0.7, 0.9];
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-3 * b.abs().max(1.)
+}
+
+fn eases() -> Vec<Box<DynEase + 'static>> {
+    vec![boxed(linear()), boxed(quad()), boxed(cubic()), boxed(quart()), boxed(quint()),
+         boxed(power(2.5)), boxed(sine()), boxed(circ()), boxed(back()), boxed(elastic()),
+         boxed(bounce()), boxed(bezier(0.17, 0.67, 0.83, 0.67))]
+}
+
+#[test]
+fn eases_match_differences() {
+    for e in eases().iter() {
+        for &mode in [In, Out, InOut, OutIn].iter() {
+            for &t in TIMES.iter() {
+                let diff = (e.ease(mode, t + H) - e.ease(mode, t - H)) / (2. * H);
+                let v = e.velocity(mode, t);
+                assert!(close(v, diff), "{} {}: velocity {} at {}, expected {}", e.name(), mode, v, t, diff);
+            }
+        }
+    }
+}
+
+/// The value `tw` writes to `val` at time `t`.
+fn value_at<T: Tween>(tw: &T, val: &Rc<Cell<f64>>, t: f64) -> f64 {
+    let mut tw = tw.clone();
+    tw.reset();
+    tw.update(t);
+    val.get()
+}
+
+fn difference<T: Tween>(tw: &T, val: &Rc<Cell<f64>>, t: f64) -> f64 {
+    (value_at(tw, val, t + H) - value_at(tw, val, t - H)) / (2. * H)
+}
+
+#[test]
+fn single() {
+    let val = Rc::new(Cell::new(0.));
+    let tw = from_to(val.clone(), 10., 30., quint(), InOut, 2.);
+    for &t in TIMES.iter() {
+        let mut at = tw.clone();
+        at.update(t * 2.);
+        let (v, diff) = (at.velocity(), difference(&tw, &val, t * 2.));
+        assert!(close(v, diff), "velocity {} at {}, expected {}", v, t * 2., diff);
+    }
+}
+
+#[test]
+fn track() {
+    let anim = Animation::from_str(include_str!("lottie/animated.json")).unwrap();
+    let curve = anim.layers[0].properties[0].dims[0].clone();
+    let val = Rc::new(Cell::new(0.));
+    let tw = Track::new(val.clone(), curve);
+    for &t in [0.3, 0.7, 1.4, 1.8].iter() {
+        let mut at = tw.clone();
+        at.update(t);
+        let (v, diff) = (at.velocity(), difference(&tw, &val, t));
+        assert!(close(v, diff), "velocity {} at {}, expected {}", v, t, diff);
+    }
+}
+
+#[test]
+fn decay_() {
+    let val = Rc::new(Cell::new(0.));
+    let tw = decay(val.clone(), 300., 4.);
+    for &t in [0.1, 0.5, 1.].iter() {
+        let mut at = tw.clone();
+        at.update(t);
+        let (v, diff) = (at.velocity(), difference(&tw, &val, t));
+        assert!(close(v, diff), "velocity {} at {}, expected {}", v, t, diff);
+    }
+}
+
+#[test]
+fn multi_without_data() {
+    let val = Rc::new(Cell::new(0.));
+    let tw = tween::series(val, vec![], linear());
+    assert_eq!(tw.velocity(), 0.);
+}
+
+
+#[test]
+fn multi_done() {
+    let val = Rc::new(Cell::new(0.));
+    let mut tw = tween::series(val, vec![(0., 1., 0.5, In), (1., 3., 0.5, In)], linear());
+    tw.update(0.75);
+    assert_eq!(tw.velocity(), 4.);
+    tw.update(0.25);
+    assert!(tw.done());
+    assert_eq!(tw.velocity(), 0.);
+}
+
+#[test]
+fn single_without_duration() {
+    let val = Rc::new(Cell::new(0.));
+    let tw = from_to(val, 0., 1., quad(), In, 0.);
+    assert_eq!(tw.velocity(), 0.);
+}
+
+/// CSS `ease-out` and `ease-in`, whose slope is 0/0 at one end point.
+#[test]
+fn bezier_end_points() {
+    let out = bezier(0., 0., 0.58, 1.);
+    let v = out.velocity_in(0.);
+    assert!(close(v, 1. / 0.58), "velocity {} at 0, expected {}", v, 1. / 0.58);
+    let in_ = bezier(0.42, 0., 1., 1.);
+    let v = in_.velocity_in(1.);
+    assert!(close(v, 1. / 0.58), "velocity {} at 1, expected {}", v, 1. / 0.58);
+}